@@ -39,9 +39,9 @@ fn main() {
                             control_flow.set_exit();
                             Ok(())
                         }
-                        G => window.set_cursor_grab(CursorGrabMode::Confined),
-                        L => window.set_cursor_grab(CursorGrabMode::Locked),
-                        A => window.set_cursor_grab(CursorGrabMode::None),
+                        G => window.set_cursor_grab(CursorGrabMode::Confined).map(|_| ()),
+                        L => window.set_cursor_grab(CursorGrabMode::Locked).map(|_| ()),
+                        A => window.set_cursor_grab(CursorGrabMode::None).map(|_| ()),
                         H => {
                             window.set_cursor_visible(modifiers.shift());
                             Ok(())
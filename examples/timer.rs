@@ -28,9 +28,13 @@ fn main() {
             Event::NewEvents(StartCause::Init) => {
                 control_flow.set_wait_until(Instant::now() + timer_length);
             }
-            Event::NewEvents(StartCause::ResumeTimeReached { .. }) => {
+            Event::NewEvents(StartCause::ResumeTimeReached {
+                requested_resume, ..
+            }) => {
+                // How late the event loop woke up, which should stay within a millisecond or so.
+                let lateness = Instant::now() - requested_resume;
                 control_flow.set_wait_until(Instant::now() + timer_length);
-                println!("\nTimer\n");
+                println!("\nTimer (woke up {:?} late)\n", lateness);
             }
             Event::WindowEvent {
                 event: WindowEvent::CloseRequested,
@@ -0,0 +1,51 @@
+#![allow(clippy::single_match)]
+
+use instant::Instant;
+use std::time::Duration;
+
+use simple_logger::SimpleLogger;
+use winit::{
+    event::{Event, StartCause},
+    event_loop::EventLoop,
+};
+
+/// How many `ControlFlow::WaitUntil` cycles are measured.
+const CYCLES: u32 = 200;
+
+const TIMER_LENGTH: Duration = Duration::from_millis(10);
+
+/// Measures how late the event loop wakes up after `ControlFlow::WaitUntil`,
+/// i.e. the time between the requested resume time and the delivery of
+/// `StartCause::ResumeTimeReached`, then prints a summary and exits.
+fn main() {
+    SimpleLogger::new().init().unwrap();
+    let event_loop = EventLoop::new();
+
+    let mut latenesses = Vec::with_capacity(CYCLES as usize);
+
+    event_loop.run(move |event, _, control_flow| match event {
+        Event::NewEvents(StartCause::Init) => {
+            control_flow.set_wait_until(Instant::now() + TIMER_LENGTH);
+        }
+        Event::NewEvents(StartCause::ResumeTimeReached {
+            requested_resume, ..
+        }) => {
+            latenesses.push(Instant::now() - requested_resume);
+            if latenesses.len() < CYCLES as usize {
+                control_flow.set_wait_until(Instant::now() + TIMER_LENGTH);
+                return;
+            }
+
+            latenesses.sort();
+            let total: Duration = latenesses.iter().sum();
+            println!("Lateness over {} cycles of {:?}:", CYCLES, TIMER_LENGTH);
+            println!("  min:    {:?}", latenesses[0]);
+            println!("  median: {:?}", latenesses[latenesses.len() / 2]);
+            println!("  mean:   {:?}", total / CYCLES);
+            println!("  p99:    {:?}", latenesses[latenesses.len() * 99 / 100]);
+            println!("  max:    {:?}", latenesses[latenesses.len() - 1]);
+            control_flow.set_exit();
+        }
+        _ => (),
+    });
+}
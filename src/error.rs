@@ -17,6 +17,16 @@ pub struct NotSupportedError {
     _marker: (),
 }
 
+/// The reason why exclusive fullscreen couldn't be entered, see
+/// [`WindowEvent::FullscreenFailed`](crate::event::WindowEvent::FullscreenFailed).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FullscreenError {
+    /// The display couldn't be captured, e.g. because another application is using it.
+    DisplayUnavailable,
+    /// The display doesn't accept the requested video mode.
+    VideoModeUnavailable,
+}
+
 /// The error type for when the OS cannot perform the requested operation.
 #[derive(Debug)]
 pub struct OsError {
@@ -65,6 +75,15 @@ impl fmt::Display for ExternalError {
     }
 }
 
+impl fmt::Display for FullscreenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            FullscreenError::DisplayUnavailable => f.pad("the display couldn't be captured"),
+            FullscreenError::VideoModeUnavailable => f.pad("the video mode couldn't be set"),
+        }
+    }
+}
+
 impl fmt::Debug for NotSupportedError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         f.debug_struct("NotSupportedError").finish()
@@ -80,3 +99,4 @@ impl fmt::Display for NotSupportedError {
 impl error::Error for OsError {}
 impl error::Error for ExternalError {}
 impl error::Error for NotSupportedError {}
+impl error::Error for FullscreenError {}
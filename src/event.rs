@@ -41,6 +41,7 @@ use std::path::PathBuf;
 use crate::window::Window;
 use crate::{
     dpi::{PhysicalPosition, PhysicalSize},
+    error::FullscreenError,
     platform_impl,
     window::{Theme, WindowId},
 };
@@ -215,6 +216,48 @@ pub enum Event<'a, T: 'static> {
     /// [`RedrawRequested`]: Self::RedrawRequested
     RedrawEventsCleared,
 
+    /// Emitted when the user or the system asks the application to quit, e.g. when choosing
+    /// "Quit" from the application menu or pressing Cmd-Q.
+    ///
+    /// This is only emitted when the application opted in to decide when to quit, e.g. after
+    /// saving its state or prompting the user to save their documents. Otherwise, the application
+    /// quits right away, and only [`LoopDestroyed`](Self::LoopDestroyed) is emitted.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted in response to `applicationShouldTerminate:` when enabled with
+    ///   `EventLoopBuilderExtMacOS::with_deferred_termination`. The application must then answer
+    ///   with `EventLoopWindowTargetExtMacOS::reply_to_quit_request`.
+    /// - **Other platforms:** Not emitted.
+    QuitRequested,
+
+    /// Emitted when the application is asked to open files, e.g. when the user opens a document
+    /// associated with the application from Finder, or drops files on its Dock icon.
+    ///
+    /// Files that the application was launched to open are emitted after
+    /// [`NewEvents(StartCause::Init)`](Self::NewEvents), so that the windows created in response
+    /// to it exist by then.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted in response to `application:openFiles:`. The application must declare
+    ///   the document types it handles in its `Info.plist`.
+    /// - **Other platforms:** Not emitted.
+    OpenFiles(Vec<PathBuf>),
+
+    /// Emitted when the user changes the accent color of the system, with the new color.
+    ///
+    /// The color is given as `(red, green, blue, alpha)` in the sRGB color space, with each
+    /// component ranging from `0.0` to `1.0`, like the one returned by
+    /// `EventLoopWindowTargetExtMacOS::system_accent_color`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted in response to `AppleColorPreferencesChangedNotification`, which is
+    ///   also posted when the highlight color changes, in which case the color may be unchanged.
+    /// - **Other platforms:** Not emitted.
+    AccentColorChanged((f64, f64, f64, f64)),
+
     /// Emitted when the event loop is being shut down.
     ///
     /// This is irreversible - if this event is emitted, it is guaranteed to be the last event that
@@ -239,6 +282,9 @@ impl<T: Clone> Clone for Event<'static, T> {
             MainEventsCleared => MainEventsCleared,
             RedrawRequested(wid) => RedrawRequested(*wid),
             RedrawEventsCleared => RedrawEventsCleared,
+            QuitRequested => QuitRequested,
+            OpenFiles(paths) => OpenFiles(paths.clone()),
+            AccentColorChanged(color) => AccentColorChanged(*color),
             LoopDestroyed => LoopDestroyed,
             Suspended => Suspended,
             Resumed => Resumed,
@@ -257,6 +303,9 @@ impl<'a, T> Event<'a, T> {
             MainEventsCleared => Ok(MainEventsCleared),
             RedrawRequested(wid) => Ok(RedrawRequested(wid)),
             RedrawEventsCleared => Ok(RedrawEventsCleared),
+            QuitRequested => Ok(QuitRequested),
+            OpenFiles(paths) => Ok(OpenFiles(paths)),
+            AccentColorChanged(color) => Ok(AccentColorChanged(color)),
             LoopDestroyed => Ok(LoopDestroyed),
             Suspended => Ok(Suspended),
             Resumed => Ok(Resumed),
@@ -277,6 +326,9 @@ impl<'a, T> Event<'a, T> {
             MainEventsCleared => Some(MainEventsCleared),
             RedrawRequested(wid) => Some(RedrawRequested(wid)),
             RedrawEventsCleared => Some(RedrawEventsCleared),
+            QuitRequested => Some(QuitRequested),
+            OpenFiles(paths) => Some(OpenFiles(paths)),
+            AccentColorChanged(color) => Some(AccentColorChanged(color)),
             LoopDestroyed => Some(LoopDestroyed),
             Suspended => Some(Suspended),
             Resumed => Some(Resumed),
@@ -327,6 +379,30 @@ pub enum WindowEvent<'a> {
     /// - **iOS / Android / Web / Wayland:** Unsupported.
     Moved(PhysicalPosition<i32>),
 
+    /// The user started moving the window, by dragging its titlebar or after a call to
+    /// [`Window::drag_window`]. The [`Moved`](Self::Moved) events emitted until the following
+    /// [`MoveEnded`](Self::MoveEnded) follow the user's drag.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted once the window starts moving, rather than when the mouse button is
+    ///   pressed.
+    /// - **Other platforms:** Never emitted.
+    MoveStarted,
+
+    /// The user stopped moving the window by releasing the mouse button.
+    ///
+    /// The window is left at its last reported position, which can be corrected from here with
+    /// [`Window::set_outer_position`], e.g. to snap the window to the edge of the screen.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted once for each [`MoveStarted`](Self::MoveStarted). Moves that don't
+    ///   involve the mouse, e.g. from the keyboard or by a window manager, end right away, with the
+    ///   first [`Moved`](Self::Moved) event while no mouse button is held.
+    /// - **Other platforms:** Never emitted.
+    MoveEnded,
+
     /// The window has been requested to close.
     CloseRequested,
 
@@ -515,6 +591,27 @@ pub enum WindowEvent<'a> {
     /// Platform-specific behavior:
     /// - **iOS / Android / Web / Wayland / Windows:** Unsupported.
     Occluded(bool),
+
+    /// Exclusive fullscreen, as requested with [`Window::set_fullscreen`], couldn't be entered.
+    ///
+    /// The window is left in the state it was in before the request.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted when the display can't be captured or the video mode can't be set.
+    /// - **Other platforms:** Never emitted.
+    FullscreenFailed(FullscreenError),
+
+    /// A drag started with `WindowExtMacOS::begin_drag_session` has ended.
+    ///
+    /// `accepted` is `false` when the drag was cancelled, or the items were dropped somewhere that
+    /// didn't accept them.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted once for each successful call to `begin_drag_session`.
+    /// - **Other platforms:** Never emitted.
+    DragSessionEnded { accepted: bool },
 }
 
 impl Clone for WindowEvent<'static> {
@@ -523,6 +620,8 @@ impl Clone for WindowEvent<'static> {
         return match self {
             Resized(size) => Resized(*size),
             Moved(pos) => Moved(*pos),
+            MoveStarted => MoveStarted,
+            MoveEnded => MoveEnded,
             CloseRequested => CloseRequested,
             Destroyed => Destroyed,
             DroppedFile(file) => DroppedFile(file.clone()),
@@ -623,6 +722,10 @@ impl Clone for WindowEvent<'static> {
                 unreachable!("Static event can't be about scale factor changing")
             }
             Occluded(occluded) => Occluded(*occluded),
+            FullscreenFailed(error) => FullscreenFailed(*error),
+            DragSessionEnded { accepted } => DragSessionEnded {
+                accepted: *accepted,
+            },
         };
     }
 }
@@ -633,6 +736,8 @@ impl<'a> WindowEvent<'a> {
         match self {
             Resized(size) => Some(Resized(size)),
             Moved(position) => Some(Moved(position)),
+            MoveStarted => Some(MoveStarted),
+            MoveEnded => Some(MoveEnded),
             CloseRequested => Some(CloseRequested),
             Destroyed => Some(Destroyed),
             DroppedFile(file) => Some(DroppedFile(file)),
@@ -727,6 +832,8 @@ impl<'a> WindowEvent<'a> {
             ThemeChanged(theme) => Some(ThemeChanged(theme)),
             ScaleFactorChanged { .. } => None,
             Occluded(occluded) => Some(Occluded(occluded)),
+            FullscreenFailed(error) => Some(FullscreenFailed(error)),
+            DragSessionEnded { accepted } => Some(DragSessionEnded { accepted }),
         }
     }
 }
@@ -811,6 +918,12 @@ pub struct KeyboardInput {
     /// This should not change if the user adjusts the host's keyboard map. Use when the physical location of the
     /// key is more important than the key's host GUI semantics, such as for movement controls in a first-person
     /// game.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** This is the virtual key code of the key (`kVK_*`), which names its position on
+    ///   an ANSI keyboard. See `platform::macos::scancode_to_physical_key` to map it to a
+    ///   [`VirtualKeyCode`].
     pub scancode: ScanCode,
 
     pub state: ElementState,
@@ -309,6 +309,8 @@ impl<T> EventLoopWindowTarget<T> {
     ///
     /// ## Platform-specific
     ///
+    /// **macOS:** The monitor with the menu bar, which may not be the first one returned by
+    /// [`available_monitors`](Self::available_monitors).
     /// **Wayland:** Always returns `None`.
     #[inline]
     pub fn primary_monitor(&self) -> Option<MonitorHandle> {
@@ -318,12 +320,14 @@ impl<T> EventLoopWindowTarget<T> {
     /// Change [`DeviceEvent`] filter mode.
     ///
     /// Since the [`DeviceEvent`] capture can lead to high CPU usage for unfocused windows, winit
-    /// will ignore them by default for unfocused windows on Linux/BSD. This method allows changing
-    /// this filter at runtime to explicitly capture them again.
+    /// will ignore them by default for unfocused windows on Linux/BSD, Windows and macOS. This
+    /// method allows changing this filter at runtime to explicitly capture them again.
     ///
     /// ## Platform-specific
     ///
-    /// - **Wayland / macOS / iOS / Android / Web:** Unsupported.
+    /// - **macOS:** Device events are only reported for the input that the application receives,
+    ///   so [`DeviceEventFilter::Never`] doesn't report the input meant for other applications.
+    /// - **Wayland / iOS / Android / Web:** Unsupported.
     ///
     /// [`DeviceEvent`]: crate::event::DeviceEvent
     pub fn set_device_event_filter(&self, _filter: DeviceEventFilter) {
@@ -333,7 +337,8 @@ impl<T> EventLoopWindowTarget<T> {
             target_os = "freebsd",
             target_os = "netbsd",
             target_os = "openbsd",
-            target_os = "windows"
+            target_os = "windows",
+            target_os = "macos"
         ))]
         self.p.set_device_event_filter(_filter);
     }
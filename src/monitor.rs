@@ -95,12 +95,30 @@ impl std::fmt::Display for VideoMode {
     }
 }
 
+/// The properties of a monitor, as returned by [`MonitorHandle::summary`].
+///
+/// Each field holds the value returned by the [`MonitorHandle`] method of the same name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+    pub name: Option<String>,
+    pub size: PhysicalSize<u32>,
+    pub position: PhysicalPosition<i32>,
+    pub scale_factor: f64,
+    pub refresh_rate_millihertz: Option<u32>,
+}
+
 /// Handle to a monitor.
 ///
 /// Allows you to retrieve information about a given monitor and can be used in [`Window`] creation.
 ///
+/// Handles to the same physical monitor compare equal and hash identically, regardless of how
+/// they were obtained (e.g. from [`Window::current_monitor`] or [`Window::available_monitors`]),
+/// so they can be used as map keys.
+///
 /// [`Window`]: crate::window::Window
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// [`Window::current_monitor`]: crate::window::Window::current_monitor
+/// [`Window::available_monitors`]: crate::window::Window::available_monitors
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct MonitorHandle {
     pub(crate) inner: platform_impl::MonitorHandle,
 }
@@ -171,4 +189,20 @@ impl MonitorHandle {
     pub fn video_modes(&self) -> impl Iterator<Item = VideoMode> {
         self.inner.video_modes()
     }
+
+    /// Returns the monitor's name, size, position, scale factor and refresh rate in a single call,
+    /// e.g. to configure a render loop.
+    ///
+    /// The values are queried one after the other, so they can still be inconsistent if the
+    /// monitor's configuration changes at the same time.
+    #[inline]
+    pub fn summary(&self) -> MonitorInfo {
+        MonitorInfo {
+            name: self.name(),
+            size: self.size(),
+            position: self.position(),
+            scale_factor: self.scale_factor(),
+            refresh_rate_millihertz: self.refresh_rate_millihertz(),
+        }
+    }
 }
@@ -1,9 +1,13 @@
-use std::os::raw::c_void;
+use std::{os::raw::c_void, path::PathBuf};
 
 use crate::{
+    dpi::{PhysicalSize, Position, Size},
+    error::ExternalError,
+    event::{KeyboardInput, ScanCode, VirtualKeyCode},
     event_loop::{EventLoopBuilder, EventLoopWindowTarget},
+    icon::Icon,
     monitor::MonitorHandle,
-    window::{Window, WindowBuilder},
+    window::{CursorIcon, Theme, Window, WindowBuilder},
 };
 
 /// Additional methods on [`Window`] that are specific to MacOS.
@@ -21,6 +25,11 @@ pub trait WindowExtMacOS {
     /// Returns whether or not the window is in simple fullscreen mode.
     fn simple_fullscreen(&self) -> bool;
 
+    /// Returns whether or not the window is fullscreen by any means, i.e. either in native
+    /// fullscreen (see [`Window::fullscreen`]) or in simple fullscreen mode (see
+    /// [`WindowExtMacOS::simple_fullscreen`]).
+    fn is_fullscreen_any(&self) -> bool;
+
     /// Toggles a fullscreen mode that doesn't require a new macOS space.
     /// Returns a boolean indicating whether the transition was successful (this
     /// won't work if the window was already in the native fullscreen).
@@ -30,43 +39,1070 @@ pub trait WindowExtMacOS {
     /// space or taking control over the entire monitor.
     fn set_simple_fullscreen(&self, fullscreen: bool) -> bool;
 
-    /// Returns whether or not the window has shadow.
-    fn has_shadow(&self) -> bool;
+    /// Returns whether the window is in the fullscreen mode set by
+    /// [`set_borderless_fullscreen_on_current_space`](Self::set_borderless_fullscreen_on_current_space).
+    fn borderless_fullscreen_on_current_space(&self) -> bool;
+
+    /// Toggles a borderless fullscreen mode that keeps the window on the current space, and the
+    /// menu bar and the Dock available to other applications. Returns whether the transition was
+    /// successful, like [`set_simple_fullscreen`](Self::set_simple_fullscreen).
+    ///
+    /// The window loses its decorations and is resized to the frame of its monitor, then raised
+    /// above the menu bar and the Dock to cover them. Unlike with
+    /// [`Fullscreen::Borderless`](crate::window::Fullscreen::Borderless), the window doesn't get
+    /// a space of its own, so other applications stay visible when they're switched to, and
+    /// their notifications and overlays still appear above it. Unlike with simple fullscreen, the
+    /// menu bar and the Dock aren't hidden for the whole application, and don't appear when the
+    /// mouse reaches the edge of the screen.
+    ///
+    /// This is a variant of simple fullscreen, so [`simple_fullscreen`](Self::simple_fullscreen)
+    /// returns `true` meanwhile, and the mode may be exited with either method. The window level
+    /// is restored upon exiting.
+    fn set_borderless_fullscreen_on_current_space(&self, fullscreen: bool) -> bool;
+
+    /// Returns whether or not the window has shadow.
+    fn has_shadow(&self) -> bool;
+
+    /// Sets whether or not the window has shadow.
+    fn set_has_shadow(&self, has_shadow: bool);
+
+    /// Returns the distance that the mouse must move before [`Window::drag_window`] drags the
+    /// window.
+    ///
+    /// See [`set_drag_threshold`](Self::set_drag_threshold).
+    fn drag_threshold(&self) -> Option<f64>;
+
+    /// Sets the distance in logical pixels that the mouse must move, with the left button
+    /// pressed, before [`Window::drag_window`] drags the window.
+    ///
+    /// This tells clicks apart from drags on a custom titlebar, e.g. to zoom the window on double
+    /// click: releasing the button before moving past the threshold doesn't drag the window, and
+    /// the release is reported as usual. Once the threshold is reached, the window catches up
+    /// with the mouse.
+    ///
+    /// The default, `None` or a distance of `0`, drags the window right away.
+    fn set_drag_threshold(&self, threshold: Option<f64>);
+
+    /// Returns whether key presses that commit text without composing are reported as
+    /// [`KeyboardInput`] while IME is allowed.
+    ///
+    /// See [`set_ime_passthrough`](Self::set_ime_passthrough).
+    ///
+    /// [`KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    fn ime_passthrough(&self) -> bool;
+
+    /// Sets whether key presses that commit text without composing are reported as
+    /// [`KeyboardInput`] while IME is allowed with [`Window::set_ime_allowed`], e.g. to handle
+    /// shortcuts in a window that also accepts text.
+    ///
+    /// While IME is allowed, key presses are routed as follows:
+    /// - A key press that starts, continues or ends a composition, i.e. while there's preedit text
+    ///   before or after it, is only reported through [`Ime`] events.
+    /// - A key press that commits text without composing, e.g. typing with a Latin keyboard
+    ///   layout, is reported as [`Ime::Commit`]. With passthrough, it's also reported as
+    ///   [`KeyboardInput`], but not as [`ReceivedCharacter`], as the text is already in the
+    ///   commit.
+    /// - Any other key press, e.g. an arrow key or a key combination handled by neither the input
+    ///   method nor the text system, is reported as [`KeyboardInput`] and [`ReceivedCharacter`].
+    ///
+    /// Key releases are reported as [`KeyboardInput`] unless there's preedit text. Passthrough has
+    /// no effect while IME isn't allowed, in which case every key press is reported as
+    /// [`KeyboardInput`]. It's disabled by default.
+    ///
+    /// [`Ime`]: crate::event::Ime
+    /// [`Ime::Commit`]: crate::event::Ime::Commit
+    /// [`KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    /// [`ReceivedCharacter`]: crate::event::WindowEvent::ReceivedCharacter
+    fn set_ime_passthrough(&self, passthrough: bool);
+
+    /// Returns the policy for key equivalents, as set by
+    /// [`set_key_equivalent_policy`](Self::set_key_equivalent_policy).
+    fn key_equivalent_policy(&self) -> KeyEquivalentPolicy;
+
+    /// Sets whether key equivalents, i.e. the key presses that are shortcuts of the menus of the
+    /// application such as Cmd-Q, are handled by the menus or reported to this window.
+    ///
+    /// With [`KeyEquivalentPolicy::Menu`], the menu item performs its action, and the key press
+    /// is only reported as [`KeyboardInput`] if no menu item has it as shortcut. With
+    /// [`KeyEquivalentPolicy::Window`], the key press is always reported, and the menus never
+    /// see it, although they can still be used with the mouse. This applies to menus set up
+    /// through [`ns_window`](Self::ns_window) as well as to the default menu.
+    ///
+    /// It's [`KeyEquivalentPolicy::Menu`] by default. See
+    /// [`set_key_equivalent_handler`](Self::set_key_equivalent_handler) to decide per key press.
+    ///
+    /// [`KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    fn set_key_equivalent_policy(&self, policy: KeyEquivalentPolicy);
+
+    /// Sets a handler deciding, per key equivalent, whether it's handled by the menus or reported
+    /// to this window, overriding [`set_key_equivalent_policy`](Self::set_key_equivalent_policy).
+    /// Pass `None` to go back to the policy.
+    ///
+    /// The handler is called on the main thread when a key with a modifier, usually Cmd, is
+    /// pressed while this window is focused, with the input that would be reported. It's called
+    /// before the event loop receives the key press, so it must not block.
+    fn set_key_equivalent_handler(
+        &self,
+        handler: Option<Box<dyn Fn(&KeyboardInput) -> KeyEquivalentPolicy + Send + Sync>>,
+    );
+
+    /// Returns the opacity of the whole window, as set by [`set_alpha`](Self::set_alpha).
+    fn alpha(&self) -> f64;
+
+    /// Sets the opacity of the whole window, including its decorations and shadow, from `0.0`
+    /// (invisible) to `1.0` (opaque), e.g. to fade a window in or out. Values outside of that
+    /// range are clamped.
+    ///
+    /// Unlike [`WindowBuilder::with_transparent`], which lets the content be drawn with an alpha
+    /// channel, this fades the window uniformly, and doesn't require the window to be
+    /// transparent. For a transparent window, both apply: the content's alpha is multiplied by
+    /// the window's opacity.
+    ///
+    /// The window is opaque by default. A window with an opacity of `0.0` is still considered
+    /// visible, see [`Window::set_visible`] to hide it.
+    fn set_alpha(&self, alpha: f64);
+
+    /// Returns whether the cursor stays visible while it's locked, as set by
+    /// [`set_cursor_visible_while_locked`](Self::set_cursor_visible_while_locked).
+    fn is_cursor_visible_while_locked(&self) -> bool;
+
+    /// Sets whether the cursor stays visible while it's grabbed with [`CursorGrabMode::Locked`].
+    ///
+    /// By default, the cursor is hidden for as long as it's locked, and shown again once the grab
+    /// is released, unless it was hidden with [`Window::set_cursor_visible`] in the meantime.
+    ///
+    /// [`CursorGrabMode::Locked`]: crate::window::CursorGrabMode::Locked
+    fn set_cursor_visible_while_locked(&self, visible: bool);
+
+    /// Returns the radius of the window's corners, as set by
+    /// [`set_corner_radius`](Self::set_corner_radius).
+    fn corner_radius(&self) -> Option<f64>;
+
+    /// Rounds the corners of the window's content with the given radius in logical pixels, or
+    /// stops rounding them when `None`.
+    ///
+    /// The content is clipped to a rounded rectangle, and the window's shadow is reshaped to
+    /// match. This is meant for borderless windows created with
+    /// [`WindowBuilder::with_transparent`], so that the clipped corners show what's behind the
+    /// window. With decorations, only the corners of the area below the titlebar are rounded.
+    ///
+    /// The view is made layer-backed if it isn't already, which is only the case before
+    /// macOS 10.13.
+    fn set_corner_radius(&self, radius: Option<f64>);
+
+    /// Returns whether or not the window can be moved by the user.
+    ///
+    /// This returns `false` while the window is in simple fullscreen mode.
+    fn is_movable(&self) -> bool;
+
+    /// Sets whether or not the window can be moved by the user.
+    ///
+    /// An immovable window can't be dragged by its titlebar, nor by its background when
+    /// [`WindowBuilderExtMacOS::with_movable_by_window_background`] is enabled. The window can
+    /// still be moved programmatically, e.g. with [`Window::set_outer_position`].
+    ///
+    /// Windows are movable by default.
+    fn set_movable(&self, movable: bool);
+
+    /// Smoothly moves and/or resizes the window over `duration` seconds.
+    ///
+    /// `position` is the new outer position of the window, like in [`Window::set_outer_position`],
+    /// and `size` is the new inner size of the window, like in [`Window::set_inner_size`]. When
+    /// only a size is given, the top-left corner of the window stays in place.
+    ///
+    /// Calling this again while an animation is in flight retargets it, starting from the
+    /// window's current frame. Frame changes made by other methods, such as
+    /// [`Window::set_outer_position`], are applied immediately, but may be overridden by an
+    /// animation that is still in flight; pass a `duration` of `0.0` to instead cancel the
+    /// animation and jump to the requested frame.
+    fn set_frame_animated<P: Into<Position>, S: Into<Size>>(
+        &self,
+        position: Option<P>,
+        size: Option<S>,
+        duration: f64,
+    );
+
+    /// Returns whether `RedrawRequested` is paced to the display's refresh rate.
+    ///
+    /// See [`set_display_link_paced`](Self::set_display_link_paced).
+    fn is_display_link_paced(&self) -> bool;
+
+    /// Paces `RedrawRequested` to the refresh rate of the display that the window is on.
+    ///
+    /// When enabled, [`Window::request_redraw`] means "draw on the next vsync": the redraw is
+    /// delivered once the display next refreshes, rather than as soon as the event loop gets
+    /// to it. Multiple requests made between two refreshes result in a single
+    /// `RedrawRequested`. This lets renderers draw at the display's refresh rate without a timer
+    /// of their own, at the cost of up to one refresh interval of added latency for each redraw.
+    ///
+    /// Redraws requested by the system, e.g. while live-resizing, are still delivered
+    /// immediately.
+    ///
+    /// To save power, the display link only runs while redraws are requested, and stops one
+    /// refresh after the last one. Restarting it after an idle period can delay the first redraw
+    /// by a little more than one refresh interval; renderers that need a steady latency should
+    /// request a redraw on every frame.
+    ///
+    /// Disabled by default.
+    fn set_display_link_paced(&self, paced: bool);
+
+    /// Shows a progress bar on the application's Dock tile, or removes it when `None`.
+    ///
+    /// `progress` ranges from `0.0` (nothing done) to `1.0` (complete); values outside of that
+    /// range are clamped, and NaN is treated as `0.0`.
+    ///
+    /// Note that the Dock tile belongs to the application, so the progress is shared between all
+    /// windows.
+    fn set_dock_progress(&self, progress: Option<f64>);
+
+    /// Returns whether or not the window's view renders at the native resolution of the display.
+    ///
+    /// This is the runtime counterpart of [`WindowBuilderExtMacOS::with_disallow_hidpi`].
+    fn allow_hidpi(&self) -> bool;
+
+    /// Sets whether or not the window's view renders at the native resolution of the display.
+    ///
+    /// When disabled, the view's surface is backed at point resolution instead (i.e. at a 1x
+    /// scale on a Retina display), which is commonly offered by games as a performance option.
+    ///
+    /// The window's [`scale_factor`] is a property of the display and does not change, so no
+    /// [`WindowEvent::ScaleFactorChanged`] is emitted. Instead, a [`WindowEvent::Resized`] with
+    /// the current inner size is emitted once the change has been applied, so that the
+    /// application can reconfigure its surface.
+    ///
+    /// [`scale_factor`]: Window::scale_factor
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
+    fn set_allow_hidpi(&self, allow_hidpi: bool);
+
+    /// Sets how the title is truncated when it doesn't fit in the titlebar.
+    ///
+    /// By default, AppKit truncates the title in the middle, i.e.
+    /// [`TitleLineBreakMode::TruncateMiddle`]. The mode is kept when the title changes.
+    /// This has no effect on windows without a titlebar.
+    fn set_title_line_break_mode(&self, mode: TitleLineBreakMode);
+
+    /// Returns whether the window catches cursor events.
+    ///
+    /// This is `false` after a call to `set_cursor_hittest(false)` made the window
+    /// click-through. That state is kept when the window's style changes, e.g. with
+    /// [`Window::set_decorations`].
+    ///
+    /// [`Window::set_decorations`]: crate::window::Window::set_decorations
+    fn is_cursor_hittest_enabled(&self) -> bool;
+
+    /// Returns the window's level, as would be passed to `setLevel:`.
+    fn window_level_raw(&self) -> i32;
+
+    /// Sets the window's level directly with `setLevel:`.
+    ///
+    /// This is an escape hatch for stacking needs that [`Window::set_always_on_top`] doesn't
+    /// cover. For instance, a window can be placed behind the desktop icons, as live wallpapers
+    /// do, by using the level returned by `CGWindowLevelForKey(kCGDesktopWindowLevelKey)`.
+    /// Levels should come from `CGWindowLevelForKey` rather than be hardcoded.
+    ///
+    /// Windows at unusual levels may behave oddly: very low levels can't be focused or clicked,
+    /// and very high ones cover system UI such as the menu bar, the Dock or even the screen
+    /// saver. This is overridden by later calls to [`Window::set_always_on_top`], and vice
+    /// versa.
+    ///
+    /// Child windows, i.e. those attached with `addChildWindow:ordered:`, stay above the window:
+    /// those at a lower level are raised to its level, both here and with
+    /// [`Window::set_always_on_top`]. They aren't lowered along with the window, and their own
+    /// level can still be set higher afterwards.
+    ///
+    /// [`Window::set_always_on_top`]: crate::window::Window::set_always_on_top
+    fn set_window_level_raw(&self, level: i32);
+
+    /// Sets the window's level to one of the common levels, with
+    /// [`set_window_level_raw`](Self::set_window_level_raw).
+    ///
+    /// See [`WindowLevelPreset`] for the caveats of the highest levels.
+    fn set_window_level_preset(&self, preset: WindowLevelPreset);
+
+    /// Returns whether the titlebar is transparent.
+    fn is_titlebar_transparent(&self) -> bool;
+
+    /// Returns whether the titlebar buttons are hidden.
+    ///
+    /// Also returns `true` when the window has no titlebar.
+    fn titlebar_buttons_hidden(&self) -> bool;
+
+    /// Hides or shows the close, minimize, zoom and full screen buttons in the titlebar, e.g. to
+    /// temporarily hide them in a presentation mode.
+    ///
+    /// This is the runtime counterpart of
+    /// [`WindowBuilderExtMacOS::with_titlebar_buttons_hidden`]. The buttons can still be
+    /// triggered through the menu and keyboard shortcuts.
+    fn set_titlebar_buttons_hidden(&self, hidden: bool);
+
+    /// Makes the titlebar transparent, so that the window's content shows through it.
+    ///
+    /// This is the runtime counterpart of [`WindowBuilderExtMacOS::with_titlebar_transparent`].
+    /// Combine it with a full-size content view to draw edge-to-edge.
+    fn set_titlebar_transparent(&self, transparent: bool);
+
+    /// Tints the controls in the window with a custom accent color, or restores the system
+    /// accent color when `None`.
+    ///
+    /// The color is given as `(red, green, blue, alpha)` in the sRGB color space, with each
+    /// component ranging from `0.0` to `1.0`. It is applied as the content tint color of the
+    /// controls that are in the window's view hierarchy when this is called, and that support it
+    /// (e.g. `NSButton` and `NSImageView`); controls added afterwards aren't affected.
+    ///
+    /// The system accent color is used wherever the custom color isn't supported: by controls
+    /// without a content tint color, and by all controls before macOS 10.14. The rest of the
+    /// window's appearance is unaffected.
+    fn set_accent_color(&self, color: Option<(f64, f64, f64, f64)>);
+
+    /// Returns the window's tabbing mode.
+    ///
+    /// See [`set_tabbing_mode`](Self::set_tabbing_mode).
+    fn tabbing_mode(&self) -> TabbingMode;
+
+    /// Sets whether the window is shown as a tab, overriding the user's "Prefer tabs" system
+    /// preference for this window only.
+    ///
+    /// Windows are only ever grouped into tabs with other windows of the application that share
+    /// the same `tabbingIdentifier`, which by default is the same for all winit windows. The
+    /// tabbing mode only affects where this window goes when it's shown: a window with
+    /// [`TabbingMode::Preferred`] is added as a tab to a window sharing its identifier, while
+    /// one with [`TabbingMode::Disallowed`] is never tabbed, and can't have other windows added
+    /// to it as tabs either.
+    ///
+    /// To disable automatic tabbing for the whole application instead, see
+    /// [`EventLoopWindowTargetExtMacOS::set_allows_automatic_window_tabbing`].
+    fn set_tabbing_mode(&self, tabbing_mode: TabbingMode);
+
+    /// Returns whether the window is left out of the application's "Window" menu.
+    fn is_excluded_from_windows_menu(&self) -> bool;
+
+    /// Sets whether the window is left out of the application's "Window" menu, e.g. for palettes
+    /// and other auxiliary windows.
+    ///
+    /// Windows are listed in the menu by default.
+    fn set_excluded_from_windows_menu(&self, excluded: bool);
+
+    /// Returns whether the window is left out of Mission Control.
+    ///
+    /// See [`set_excluded_from_mission_control`](Self::set_excluded_from_mission_control).
+    fn is_excluded_from_mission_control(&self) -> bool;
+
+    /// Sets whether the window is left out of Mission Control, e.g. for HUDs, overlays and other
+    /// short-lived windows.
+    ///
+    /// An excluded window is hidden while Mission Control is shown, follows the user to the
+    /// active space, and is skipped when cycling through windows with <kbd>Cmd+`</kbd>.
+    ///
+    /// Windows are shown in Mission Control by default.
+    fn set_excluded_from_mission_control(&self, excluded: bool);
+
+    /// Returns whether the window can be shown over the fullscreen windows of other applications.
+    ///
+    /// See [`set_fullscreen_auxiliary`](Self::set_fullscreen_auxiliary).
+    fn is_fullscreen_auxiliary(&self) -> bool;
+
+    /// Sets whether the window can be shown over the fullscreen windows of other applications, in
+    /// their space, e.g. for overlays showing a chat or statistics over a game.
+    ///
+    /// This sets `NSWindowCollectionBehaviorFullScreenAuxiliary`, and also makes the window follow
+    /// the user to the active space, so that it's shown in the fullscreen space that the user is
+    /// in. An auxiliary window can't be made fullscreen itself with the green button.
+    ///
+    /// Note that macOS only shows the windows of the active application over the fullscreen window
+    /// of another one if the former is an agent, e.g. with
+    /// [`ActivationPolicy::Accessory`](crate::platform::macos::ActivationPolicy::Accessory) or
+    /// `LSUIElement` in its `Info.plist`. Otherwise, activating the application switches to the
+    /// space of its windows instead. The window should also be raised above the fullscreen window,
+    /// e.g. with [`Window::set_always_on_top`].
+    ///
+    /// This is disabled by default.
+    ///
+    /// [`Window::set_always_on_top`]: crate::window::Window::set_always_on_top
+    fn set_fullscreen_auxiliary(&self, auxiliary: bool);
+
+    /// Returns the name of the appearance that the window is drawn with, e.g.
+    /// `"NSAppearanceNameDarkAqua"`.
+    ///
+    /// Unlike a plain light or dark theme, this tells apart the accessibility variants that are
+    /// used when "Increase contrast" is enabled, e.g.
+    /// `"NSAppearanceNameAccessibilityHighContrastDarkAqua"`, so that custom controls can match
+    /// them.
+    ///
+    /// Always returns `"NSAppearanceNameAqua"` before macOS 10.14.
+    fn effective_appearance_name(&self) -> String;
+
+    /// Returns whether the window is drawn with a light or a dark appearance.
+    ///
+    /// Always returns [`Theme::Light`] before macOS 10.14.
+    fn theme(&self) -> Theme;
+
+    /// Sets the appearance of the window, including the blur set by
+    /// [`set_blur_material`](Self::set_blur_material). Pass `None` to follow the system's
+    /// appearance again, which is the default.
+    ///
+    /// The dark appearance needs macOS 10.14 or later, before which this has no effect.
+    fn set_theme(&self, theme: Option<Theme>);
+
+    /// Returns whether the inner size of the window is limited to the work area of its monitor.
+    ///
+    /// See [`set_constrained_to_monitor`](Self::set_constrained_to_monitor).
+    fn is_constrained_to_monitor(&self) -> bool;
+
+    /// Sets whether the inner size of the window is limited to the work area of its monitor, i.e.
+    /// the part of the monitor that isn't covered by the menu bar and the Dock, less the title
+    /// bar of the window.
+    ///
+    /// The limit applies on top of [`Window::set_max_inner_size`], and follows the window when
+    /// it's moved to another monitor, i.e. once most of the window is on the other monitor. The
+    /// window is then shrunk if it's larger than the new limit, keeping its top-left corner in
+    /// place, but isn't enlarged when the limit grows.
+    ///
+    /// This is disabled by default.
+    fn set_constrained_to_monitor(&self, constrained: bool);
+
+    /// Returns whether the window is redrawn synchronously when it's resized.
+    ///
+    /// See [`set_synchronous_resize`](Self::set_synchronous_resize).
+    fn synchronous_resize(&self) -> bool;
+
+    /// Sets whether the window is redrawn synchronously when it's resized, to avoid the flicker
+    /// or the stretched contents that show during a live resize when the window is displayed at
+    /// its new size before the application redraws.
+    ///
+    /// When enabled, each resize delivers `WindowEvent::Resized` then
+    /// [`Event::RedrawRequested`] right away, from within the resize, rather than on the next
+    /// iteration of the event loop. The application must redraw and present the new frame while
+    /// handling [`Event::RedrawRequested`], before returning: the window is displayed at its new
+    /// size as soon as it does. With a `CAMetalLayer`, setting its `presentsWithTransaction` for
+    /// the duration of the resize makes the frame show up along with the new size.
+    ///
+    /// Resizes caused by the application from its event handler, e.g. with
+    /// [`Window::set_inner_size`], can't be redrawn synchronously, and request a redraw instead.
+    ///
+    /// This is disabled by default.
+    ///
+    /// [`Event::RedrawRequested`]: crate::event::Event::RedrawRequested
+    fn set_synchronous_resize(&self, synchronous_resize: bool);
+
+    /// Closes the window without dropping it, emitting [`WindowEvent::Destroyed`].
+    ///
+    /// Windows are otherwise closed when they're dropped: the close button only emits
+    /// [`WindowEvent::CloseRequested`], and it's up to the application to drop the window in
+    /// response. This doesn't emit [`WindowEvent::CloseRequested`], as the application already
+    /// decided to close the window.
+    ///
+    /// The window stays valid until it's dropped, but no other event is emitted for it, so it can't
+    /// be shown again: [`Window::set_visible(true)`](crate::window::Window::set_visible) and
+    /// [`Window::focus_window`](crate::window::Window::focus_window) do nothing afterwards.
+    /// Dropping it afterwards does nothing more.
+    ///
+    /// [`WindowEvent::Destroyed`]: crate::event::WindowEvent::Destroyed
+    /// [`WindowEvent::CloseRequested`]: crate::event::WindowEvent::CloseRequested
+    fn close(&self);
+
+    /// Sets the label that VoiceOver announces for the window, instead of its title.
+    fn set_accessibility_label(&self, label: &str);
+
+    /// Sets the accessibility role of the window's content, e.g. `"AXGroup"` or `"AXImage"`, which
+    /// must be one of the `NSAccessibilityRole` values.
+    ///
+    /// By default, the content isn't exposed to accessibility clients, and VoiceOver skips it.
+    /// Once it has a role, VoiceOver announces the content as an element of that role.
+    fn set_accessibility_role(&self, role: &str);
+
+    /// Sets both the minimum and the maximum size of the window's content at once.
+    ///
+    /// Like [`Window::set_min_inner_size`] and [`Window::set_max_inner_size`], this constrains
+    /// the content area directly, whatever the size of the title bar. Setting both at once
+    /// applies them as a pair: setting a minimum larger than the current maximum on its own would
+    /// clamp it down to that maximum, even if a larger maximum is set right after.
+    ///
+    /// A minimum that's larger than the maximum is still clamped down to it.
+    fn set_content_size_limits(&self, min: Option<Size>, max: Option<Size>);
+
+    /// Returns the insets of the safe area of the window's content, in physical pixels.
+    ///
+    /// The safe area is the part of the content that isn't covered by the title bar, e.g. with
+    /// [`with_fullsize_content_view`](WindowBuilderExtMacOS::with_fullsize_content_view), nor by
+    /// the camera housing (the "notch") of the built-in display of some MacBooks, e.g. in
+    /// fullscreen.
+    fn safe_area_insets(&self) -> SafeAreaInsets;
+
+    /// Returns the size of the safe area of the window's content, see
+    /// [`safe_area_insets`](Self::safe_area_insets).
+    ///
+    /// This is always [`Window::inner_size`] less the insets.
+    fn inner_size_excluding_insets(&self) -> PhysicalSize<u32>;
+
+    /// Confines the cursor to a part of the window's content, given by its position and its size
+    /// relative to the top-left corner of the content, or stops confining it with `None`.
+    ///
+    /// While the window is focused, the cursor is moved back to the closest point of the rect
+    /// whenever it leaves it, and `WindowEvent::CursorMoved` reports that point. The cursor is
+    /// moved inside right away if it's outside when this is called.
+    ///
+    /// The part of the rect that's outside of the content is ignored, also as the window is
+    /// resized, and the cursor isn't confined while the rect doesn't overlap the content at all.
+    fn confine_cursor_to_rect(&self, rect: Option<(Position, Size)>);
+
+    /// Returns whether the system has a cursor for `icon`, rather than
+    /// [`Window::set_cursor_icon`] using a fallback for it.
+    ///
+    /// Some cursors are only available through private AppKit API, and may be missing from some
+    /// versions of macOS, in which case they're replaced as follows:
+    ///
+    /// - `NeResize`, `SwResize`: `NeswResize`, then `Move`.
+    /// - `NwResize`, `SeResize`: `NwseResize`, then `Move`.
+    /// - `NeswResize`, `NwseResize`: `Move`.
+    /// - `Help`, `ZoomIn`, `ZoomOut`, `Wait`, `Progress`: `Arrow`.
+    ///
+    /// All the other cursors are always available, though some share the same look, e.g.
+    /// `NotAllowed` and `NoDrop`.
+    ///
+    /// [`Window::set_cursor_icon`]: crate::window::Window::set_cursor_icon
+    fn is_cursor_icon_supported(&self, icon: CursorIcon) -> bool;
+
+    /// Returns the height of the title bar, including the toolbar if there's one, in logical
+    /// pixels.
+    ///
+    /// This is the height of the top part of the window that content shouldn't be drawn under, so
+    /// that it doesn't collide with the title or the traffic light buttons, e.g. when the content
+    /// view is extended under the title bar with
+    /// [`with_fullsize_content_view`](WindowBuilderExtMacOS::with_fullsize_content_view).
+    ///
+    /// Returns `0.0` for windows without a title bar.
+    fn titlebar_height(&self) -> f64;
+
+    /// Returns whether [`Window::request_redraw`] delivers redraws while the window is occluded.
+    ///
+    /// See [`set_redraw_while_occluded`](Self::set_redraw_while_occluded).
+    fn redraws_while_occluded(&self) -> bool;
+
+    /// Sets whether [`Window::request_redraw`] delivers redraws while the window is occluded,
+    /// i.e. fully hidden by other windows, minimized, or on another space.
+    ///
+    /// By default, redraws requested while the window is occluded are deferred until it's
+    /// revealed, which is signaled by `WindowEvent::Occluded(false)`, so that no time is spent
+    /// drawing frames that can't be seen. Multiple requests result in a single redraw. Enable this
+    /// to keep redrawing anyway, e.g. to keep rendering to a video capture.
+    ///
+    /// Redraws requested by the system, e.g. when the window is resized, are always delivered.
+    fn set_redraw_while_occluded(&self, enabled: bool);
+
+    /// Returns the scale of the window's surface.
+    ///
+    /// See [`set_surface_scale`](Self::set_surface_scale). Until that's called, this is the
+    /// window's [`scale_factor`](Window::scale_factor).
+    fn surface_scale(&self) -> f64;
+
+    /// Sets the number of pixels per point of the window's surface, independently of the
+    /// window's [`scale_factor`](Window::scale_factor), e.g. to render at a lower resolution for
+    /// performance. The surface is stretched to fill the window.
+    ///
+    /// This sets the `contentsScale` of the view's layer, so it applies to surfaces backed by that
+    /// layer, such as a `CAMetalLayer`, and should be called once the surface has been attached
+    /// to the view. The resolution of OpenGL surfaces is instead chosen by
+    /// [`WindowBuilderExtMacOS::with_disallow_hidpi`].
+    ///
+    /// [`Window::inner_size`] is unaffected and keeps being reported at the window's scale
+    /// factor, so the size of the surface in pixels is
+    /// `inner_size * surface_scale / scale_factor`. The scale is kept when the window moves to a
+    /// display with a different scale factor.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `scale` isn't positive and finite.
+    fn set_surface_scale(&self, scale: f64);
+
+    /// Blurs what's behind the window with the given material, like the sidebars and popovers of
+    /// native applications, or removes the blur when `None`.
+    ///
+    /// The blur is drawn behind the window's content, including the titlebar, so it's only
+    /// visible through the parts of the content that are transparent. The window must be created
+    /// with [`WindowBuilder::with_transparent`], and the content must be drawn with an alpha
+    /// channel. Materials that need a newer version of macOS than the running one are replaced
+    /// with an appearance-based material.
+    ///
+    /// The material adapts to the window's appearance, and is drawn inactive while the window
+    /// isn't the key window, like native windows.
+    fn set_blur_material(&self, material: Option<BlurMaterial>);
+
+    /// Sets whether the blur set by [`set_blur_material`](Self::set_blur_material) shows what's
+    /// behind the window, or what's drawn in the window below its content.
+    ///
+    /// Defaults to [`BlurBlendingMode::BehindWindow`].
+    fn set_blur_blending_mode(&self, blending_mode: BlurBlendingMode);
+
+    /// Starts dragging `items` out of the window, e.g. into Finder or another application.
+    ///
+    /// `image` is shown under the cursor while dragging, at its native resolution on the
+    /// window's display, with the pixel at `hotspot` (measured from the image's top-left corner)
+    /// under the cursor. The items are always copied; the drag never moves them.
+    ///
+    /// [`WindowEvent::DragSessionEnded`] is emitted once the items are dropped or the drag is
+    /// cancelled. Does nothing if `items` is empty.
+    ///
+    /// This should be called on the main thread, in response to a mouse button being pressed or
+    /// the mouse being dragged, like [`Window::drag_window`].
+    ///
+    /// [`WindowEvent::DragSessionEnded`]: crate::event::WindowEvent::DragSessionEnded
+    fn begin_drag_session(
+        &self,
+        items: Vec<DragItem>,
+        image: Icon,
+        hotspot: (u32, u32),
+    ) -> Result<(), ExternalError>;
+
+    /// Returns whether the window is presented as a sheet, see
+    /// [`begin_as_sheet`](Self::begin_as_sheet).
+    fn is_sheet(&self) -> bool;
+
+    /// Presents the window as a sheet of `parent`, sliding down from its titlebar.
+    ///
+    /// The sheet is modal to `parent`, which doesn't receive input until the sheet is ended, while
+    /// the other windows of the application keep working. The window is hidden first, and shown by
+    /// `parent`; if `parent` already presents a sheet, the window is shown once that sheet ends.
+    ///
+    /// The sheet stays until [`end_sheet`](Self::end_sheet) is called or the window is dropped:
+    /// the user can't dismiss it, so the application must end it e.g. when one of the buttons
+    /// drawn in it is clicked. [`is_sheet`](Self::is_sheet) returns `false` once it's ended.
+    fn begin_as_sheet(&self, parent: &Window);
+
+    /// Ends the sheet that the window is presented as, which hides it and gives input back to its
+    /// parent. Does nothing if the window isn't presented as a sheet.
+    ///
+    /// The window isn't destroyed, and can be presented as a sheet again.
+    fn end_sheet(&self);
+
+    /// Returns whether the window prevents the application from quitting while it's modal.
+    ///
+    /// See [`set_prevents_termination_when_modal`](Self::set_prevents_termination_when_modal).
+    fn prevents_termination_when_modal(&self) -> bool;
+
+    /// Sets whether the window prevents the application from quitting while it's modal, with
+    /// `setPreventsApplicationTerminationWhenModal:`.
+    ///
+    /// This only applies while the window is application-modal, e.g. when it's run with
+    /// `runModalForWindow:` through [`ns_window`](Self::ns_window). Quitting is then disabled
+    /// altogether: the Quit menu item and Cmd-Q do nothing, and [`Event::QuitRequested`] isn't
+    /// emitted. When this is disabled, the application is asked to quit as usual: it quits right
+    /// away, or emits [`Event::QuitRequested`] with
+    /// [`EventLoopBuilderExtMacOS::with_deferred_termination`].
+    ///
+    /// Sheets, see [`begin_as_sheet`](Self::begin_as_sheet), are only modal to their parent and
+    /// never prevent quitting; to keep the application running while one is presented, enable
+    /// deferred termination and answer [`Event::QuitRequested`] with `false`.
+    ///
+    /// This is enabled by default.
+    ///
+    /// [`Event::QuitRequested`]: crate::event::Event::QuitRequested
+    fn set_prevents_termination_when_modal(&self, prevents: bool);
+}
+
+impl WindowExtMacOS for Window {
+    #[inline]
+    fn ns_window(&self) -> *mut c_void {
+        self.window.ns_window()
+    }
+
+    #[inline]
+    fn ns_view(&self) -> *mut c_void {
+        self.window.ns_view()
+    }
+
+    #[inline]
+    fn simple_fullscreen(&self) -> bool {
+        self.window.simple_fullscreen()
+    }
+
+    #[inline]
+    fn is_fullscreen_any(&self) -> bool {
+        self.window.is_fullscreen_any()
+    }
+
+    #[inline]
+    fn set_simple_fullscreen(&self, fullscreen: bool) -> bool {
+        self.window.set_simple_fullscreen(fullscreen)
+    }
+
+    #[inline]
+    fn borderless_fullscreen_on_current_space(&self) -> bool {
+        self.window.borderless_fullscreen_on_current_space()
+    }
+
+    #[inline]
+    fn set_borderless_fullscreen_on_current_space(&self, fullscreen: bool) -> bool {
+        self.window
+            .set_borderless_fullscreen_on_current_space(fullscreen)
+    }
+
+    #[inline]
+    fn has_shadow(&self) -> bool {
+        self.window.has_shadow()
+    }
+
+    #[inline]
+    fn set_has_shadow(&self, has_shadow: bool) {
+        self.window.set_has_shadow(has_shadow)
+    }
+
+    #[inline]
+    fn drag_threshold(&self) -> Option<f64> {
+        self.window.drag_threshold()
+    }
+
+    #[inline]
+    fn set_drag_threshold(&self, threshold: Option<f64>) {
+        self.window.set_drag_threshold(threshold)
+    }
+
+    #[inline]
+    fn ime_passthrough(&self) -> bool {
+        self.window.ime_passthrough()
+    }
+
+    #[inline]
+    fn set_ime_passthrough(&self, passthrough: bool) {
+        self.window.set_ime_passthrough(passthrough)
+    }
+
+    #[inline]
+    fn key_equivalent_policy(&self) -> KeyEquivalentPolicy {
+        self.window.key_equivalent_policy()
+    }
+
+    #[inline]
+    fn set_key_equivalent_policy(&self, policy: KeyEquivalentPolicy) {
+        self.window.set_key_equivalent_policy(policy)
+    }
+
+    #[inline]
+    fn set_key_equivalent_handler(
+        &self,
+        handler: Option<Box<dyn Fn(&KeyboardInput) -> KeyEquivalentPolicy + Send + Sync>>,
+    ) {
+        self.window.set_key_equivalent_handler(handler)
+    }
+
+    #[inline]
+    fn alpha(&self) -> f64 {
+        self.window.alpha()
+    }
+
+    #[inline]
+    fn set_alpha(&self, alpha: f64) {
+        self.window.set_alpha(alpha)
+    }
+
+    #[inline]
+    fn is_cursor_visible_while_locked(&self) -> bool {
+        self.window.is_cursor_visible_while_locked()
+    }
+
+    #[inline]
+    fn set_cursor_visible_while_locked(&self, visible: bool) {
+        self.window.set_cursor_visible_while_locked(visible)
+    }
+
+    #[inline]
+    fn corner_radius(&self) -> Option<f64> {
+        self.window.corner_radius()
+    }
+
+    #[inline]
+    fn set_corner_radius(&self, radius: Option<f64>) {
+        self.window.set_corner_radius(radius)
+    }
+
+    #[inline]
+    fn is_movable(&self) -> bool {
+        self.window.is_movable()
+    }
+
+    #[inline]
+    fn set_movable(&self, movable: bool) {
+        self.window.set_movable(movable)
+    }
+
+    #[inline]
+    fn set_frame_animated<P: Into<Position>, S: Into<Size>>(
+        &self,
+        position: Option<P>,
+        size: Option<S>,
+        duration: f64,
+    ) {
+        self.window.set_frame_animated(position, size, duration)
+    }
+
+    #[inline]
+    fn is_display_link_paced(&self) -> bool {
+        self.window.is_display_link_paced()
+    }
+
+    #[inline]
+    fn set_display_link_paced(&self, paced: bool) {
+        self.window.set_display_link_paced(paced)
+    }
+
+    #[inline]
+    fn set_dock_progress(&self, progress: Option<f64>) {
+        self.window.set_dock_progress(progress)
+    }
+
+    #[inline]
+    fn allow_hidpi(&self) -> bool {
+        self.window.allow_hidpi()
+    }
+
+    #[inline]
+    fn set_allow_hidpi(&self, allow_hidpi: bool) {
+        self.window.set_allow_hidpi(allow_hidpi)
+    }
+
+    #[inline]
+    fn set_title_line_break_mode(&self, mode: TitleLineBreakMode) {
+        self.window.set_title_line_break_mode(mode)
+    }
+
+    #[inline]
+    fn is_cursor_hittest_enabled(&self) -> bool {
+        self.window.is_cursor_hittest_enabled()
+    }
+
+    #[inline]
+    fn window_level_raw(&self) -> i32 {
+        self.window.window_level_raw()
+    }
+
+    #[inline]
+    fn set_window_level_raw(&self, level: i32) {
+        self.window.set_window_level_raw(level)
+    }
+
+    #[inline]
+    fn set_window_level_preset(&self, preset: WindowLevelPreset) {
+        self.window.set_window_level_preset(preset)
+    }
+
+    #[inline]
+    fn is_titlebar_transparent(&self) -> bool {
+        self.window.is_titlebar_transparent()
+    }
+
+    #[inline]
+    fn titlebar_buttons_hidden(&self) -> bool {
+        self.window.titlebar_buttons_hidden()
+    }
+
+    #[inline]
+    fn set_titlebar_buttons_hidden(&self, hidden: bool) {
+        self.window.set_titlebar_buttons_hidden(hidden)
+    }
+
+    #[inline]
+    fn set_titlebar_transparent(&self, transparent: bool) {
+        self.window.set_titlebar_transparent(transparent)
+    }
 
-    /// Sets whether or not the window has shadow.
-    fn set_has_shadow(&self, has_shadow: bool);
-}
+    #[inline]
+    fn set_accent_color(&self, color: Option<(f64, f64, f64, f64)>) {
+        self.window.set_accent_color(color)
+    }
 
-impl WindowExtMacOS for Window {
     #[inline]
-    fn ns_window(&self) -> *mut c_void {
-        self.window.ns_window()
+    fn tabbing_mode(&self) -> TabbingMode {
+        self.window.tabbing_mode()
     }
 
     #[inline]
-    fn ns_view(&self) -> *mut c_void {
-        self.window.ns_view()
+    fn set_tabbing_mode(&self, tabbing_mode: TabbingMode) {
+        self.window.set_tabbing_mode(tabbing_mode)
     }
 
     #[inline]
-    fn simple_fullscreen(&self) -> bool {
-        self.window.simple_fullscreen()
+    fn is_excluded_from_windows_menu(&self) -> bool {
+        self.window.is_excluded_from_windows_menu()
     }
 
     #[inline]
-    fn set_simple_fullscreen(&self, fullscreen: bool) -> bool {
-        self.window.set_simple_fullscreen(fullscreen)
+    fn set_excluded_from_windows_menu(&self, excluded: bool) {
+        self.window.set_excluded_from_windows_menu(excluded)
     }
 
     #[inline]
-    fn has_shadow(&self) -> bool {
-        self.window.has_shadow()
+    fn is_excluded_from_mission_control(&self) -> bool {
+        self.window.is_excluded_from_mission_control()
     }
 
     #[inline]
-    fn set_has_shadow(&self, has_shadow: bool) {
-        self.window.set_has_shadow(has_shadow)
+    fn set_excluded_from_mission_control(&self, excluded: bool) {
+        self.window.set_excluded_from_mission_control(excluded)
+    }
+
+    #[inline]
+    fn is_fullscreen_auxiliary(&self) -> bool {
+        self.window.is_fullscreen_auxiliary()
+    }
+
+    #[inline]
+    fn set_fullscreen_auxiliary(&self, auxiliary: bool) {
+        self.window.set_fullscreen_auxiliary(auxiliary)
+    }
+
+    #[inline]
+    fn effective_appearance_name(&self) -> String {
+        self.window.effective_appearance_name()
+    }
+
+    #[inline]
+    fn theme(&self) -> Theme {
+        self.window.theme()
+    }
+
+    #[inline]
+    fn set_theme(&self, theme: Option<Theme>) {
+        self.window.set_theme(theme)
+    }
+
+    #[inline]
+    fn is_constrained_to_monitor(&self) -> bool {
+        self.window.is_constrained_to_monitor()
+    }
+
+    #[inline]
+    fn set_constrained_to_monitor(&self, constrained: bool) {
+        self.window.set_constrained_to_monitor(constrained)
+    }
+
+    #[inline]
+    fn synchronous_resize(&self) -> bool {
+        self.window.synchronous_resize()
+    }
+
+    #[inline]
+    fn set_synchronous_resize(&self, synchronous_resize: bool) {
+        self.window.set_synchronous_resize(synchronous_resize)
+    }
+
+    #[inline]
+    fn close(&self) {
+        self.window.close()
+    }
+
+    #[inline]
+    fn set_accessibility_label(&self, label: &str) {
+        self.window.set_accessibility_label(label)
+    }
+
+    #[inline]
+    fn set_accessibility_role(&self, role: &str) {
+        self.window.set_accessibility_role(role)
+    }
+
+    #[inline]
+    fn set_content_size_limits(&self, min: Option<Size>, max: Option<Size>) {
+        self.window.set_content_size_limits(min, max)
+    }
+
+    #[inline]
+    fn safe_area_insets(&self) -> SafeAreaInsets {
+        self.window.safe_area_insets()
+    }
+
+    #[inline]
+    fn inner_size_excluding_insets(&self) -> PhysicalSize<u32> {
+        self.window.inner_size_excluding_insets()
+    }
+
+    #[inline]
+    fn confine_cursor_to_rect(&self, rect: Option<(Position, Size)>) {
+        self.window.confine_cursor_to_rect(rect)
+    }
+
+    #[inline]
+    fn is_cursor_icon_supported(&self, icon: CursorIcon) -> bool {
+        self.window.is_cursor_icon_supported(icon)
+    }
+
+    #[inline]
+    fn titlebar_height(&self) -> f64 {
+        self.window.titlebar_height()
+    }
+
+    #[inline]
+    fn redraws_while_occluded(&self) -> bool {
+        self.window.redraws_while_occluded()
+    }
+
+    #[inline]
+    fn set_redraw_while_occluded(&self, enabled: bool) {
+        self.window.set_redraw_while_occluded(enabled)
+    }
+
+    #[inline]
+    fn surface_scale(&self) -> f64 {
+        self.window.surface_scale()
+    }
+
+    #[inline]
+    fn set_surface_scale(&self, scale: f64) {
+        self.window.set_surface_scale(scale)
+    }
+
+    #[inline]
+    fn set_blur_material(&self, material: Option<BlurMaterial>) {
+        self.window.set_blur_material(material)
+    }
+
+    #[inline]
+    fn set_blur_blending_mode(&self, blending_mode: BlurBlendingMode) {
+        self.window.set_blur_blending_mode(blending_mode)
     }
+
+    #[inline]
+    fn begin_drag_session(
+        &self,
+        items: Vec<DragItem>,
+        image: Icon,
+        hotspot: (u32, u32),
+    ) -> Result<(), ExternalError> {
+        self.window.begin_drag_session(items, image, hotspot)
+    }
+
+    #[inline]
+    fn is_sheet(&self) -> bool {
+        self.window.is_sheet()
+    }
+
+    #[inline]
+    fn begin_as_sheet(&self, parent: &Window) {
+        self.window.begin_as_sheet(parent)
+    }
+
+    #[inline]
+    fn end_sheet(&self) {
+        self.window.end_sheet()
+    }
+
+    #[inline]
+    fn prevents_termination_when_modal(&self) -> bool {
+        self.window.prevents_termination_when_modal()
+    }
+
+    #[inline]
+    fn set_prevents_termination_when_modal(&self, prevents: bool) {
+        self.window.set_prevents_termination_when_modal(prevents)
+    }
+}
+
+/// An item dragged out of a window, see [`WindowExtMacOS::begin_drag_session`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DragItem {
+    /// A file or directory, dropped as its file URL.
+    File(PathBuf),
+    /// Plain text.
+    Text(String),
 }
 
 /// Corresponds to `NSApplicationActivationPolicy`.
@@ -86,6 +1122,146 @@ impl Default for ActivationPolicy {
     }
 }
 
+/// How the title is truncated when it's too long, see
+/// [`WindowExtMacOS::set_title_line_break_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TitleLineBreakMode {
+    /// Corresponds to `NSLineBreakByTruncatingHead`: "…ong title".
+    TruncateHead,
+    /// Corresponds to `NSLineBreakByTruncatingMiddle`: "A lo…itle".
+    TruncateMiddle,
+    /// Corresponds to `NSLineBreakByTruncatingTail`: "A long t…". Useful to keep the file name
+    /// visible when the title is a path.
+    TruncateTail,
+    /// Corresponds to `NSLineBreakByClipping`: the title is cut off without an ellipsis.
+    Clip,
+}
+
+impl Default for TitleLineBreakMode {
+    fn default() -> Self {
+        TitleLineBreakMode::TruncateMiddle
+    }
+}
+
+/// Corresponds to `NSVisualEffectMaterial`, see [`WindowExtMacOS::set_blur_material`].
+///
+/// The materials are named after the part of the interface they're meant for, and look the same
+/// as it in the current appearance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlurMaterial {
+    /// The material of window titlebars.
+    Titlebar,
+    /// The material of selected content, e.g. highlighted rows in a table.
+    Selection,
+    /// The material of menus.
+    Menu,
+    /// The material of popovers.
+    Popover,
+    /// The material of window sidebars.
+    Sidebar,
+    /// The material of inline header or footer views. Requires macOS 10.14.
+    HeaderView,
+    /// The material of sheets. Requires macOS 10.14.
+    Sheet,
+    /// The material of opaque window backgrounds. Requires macOS 10.14.
+    WindowBackground,
+    /// The material of heads-up display windows. Requires macOS 10.14.
+    HudWindow,
+    /// The material of full-screen modal interfaces. Requires macOS 10.14.
+    FullScreenUi,
+    /// The material of tooltips. Requires macOS 10.14.
+    ToolTip,
+    /// The material of opaque content backgrounds. Requires macOS 10.14.
+    ContentBackground,
+    /// The material shown under window backgrounds. Requires macOS 10.14.
+    UnderWindowBackground,
+    /// The material shown under the page of document-based applications. Requires macOS 10.14.
+    UnderPageBackground,
+}
+
+/// Corresponds to `NSVisualEffectBlendingMode`, see [`WindowExtMacOS::set_blur_blending_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlurBlendingMode {
+    /// Corresponds to `NSVisualEffectBlendingModeBehindWindow`: blurs the desktop and the other
+    /// windows behind the window.
+    BehindWindow,
+    /// Corresponds to `NSVisualEffectBlendingModeWithinWindow`: blurs what's drawn in the window
+    /// below its content.
+    WithinWindow,
+}
+
+impl Default for BlurBlendingMode {
+    fn default() -> Self {
+        BlurBlendingMode::BehindWindow
+    }
+}
+
+/// Common window levels, in increasing order, see [`WindowExtMacOS::set_window_level_preset`].
+///
+/// Each level is the one returned by `CGWindowLevelForKey` for the corresponding key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowLevelPreset {
+    /// Corresponds to `kCGNormalWindowLevelKey`: the level of regular windows.
+    Normal,
+    /// Corresponds to `kCGFloatingWindowLevelKey`: above regular windows, like palettes and
+    /// [`Window::set_always_on_top`](crate::window::Window::set_always_on_top).
+    Floating,
+    /// Corresponds to `kCGStatusWindowLevelKey`: the level of status items, above the menu bar.
+    Status,
+    /// Corresponds to `kCGPopUpMenuWindowLevelKey`: the level of pop-up menus.
+    PopUpMenu,
+    /// Corresponds to `kCGScreenSaverWindowLevelKey`: the level of screen savers, above the menu
+    /// bar, the Dock and other applications' windows.
+    ///
+    /// The window still isn't shown over the lock screen or the login window, which are drawn by
+    /// the system in another session. To also cover fullscreen applications, the window must be
+    /// visible on all spaces.
+    ScreenSaver,
+    /// Corresponds to `kCGMaximumWindowLevelKey`: the highest level, above everything the
+    /// application can draw over, including the cursor.
+    ///
+    /// The same restrictions as [`WindowLevelPreset::ScreenSaver`] apply.
+    Maximum,
+}
+
+/// Corresponds to `NSWindowTabbingMode`.
+///
+/// See [`WindowExtMacOS::set_tabbing_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TabbingMode {
+    /// Corresponds to `NSWindowTabbingModeAutomatic`: the window is tabbed according to the
+    /// user's "Prefer tabs" system preference.
+    Automatic,
+    /// Corresponds to `NSWindowTabbingModePreferred`: the window is always tabbed, if there's a
+    /// window to add it to.
+    Preferred,
+    /// Corresponds to `NSWindowTabbingModeDisallowed`: the window is never tabbed.
+    Disallowed,
+}
+
+impl Default for TabbingMode {
+    fn default() -> Self {
+        TabbingMode::Automatic
+    }
+}
+
+/// Whether key equivalents are handled by the menus or reported to the window.
+///
+/// See [`WindowExtMacOS::set_key_equivalent_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyEquivalentPolicy {
+    /// The menus handle the key equivalents they have a menu item for.
+    Menu,
+    /// The window receives all key equivalents.
+    Window,
+}
+
+impl Default for KeyEquivalentPolicy {
+    fn default() -> Self {
+        KeyEquivalentPolicy::Menu
+    }
+}
+
 /// Additional methods on [`WindowBuilder`] that are specific to MacOS.
 ///
 /// **Note:** Properties dealing with the titlebar will be overwritten by the [`WindowBuilder::with_decorations`] method:
@@ -110,6 +1286,16 @@ pub trait WindowBuilderExtMacOS {
     fn with_fullsize_content_view(self, fullsize_content_view: bool) -> WindowBuilder;
     fn with_disallow_hidpi(self, disallow_hidpi: bool) -> WindowBuilder;
     fn with_has_shadow(self, has_shadow: bool) -> WindowBuilder;
+    /// Sets the window's tabbing mode, see [`WindowExtMacOS::set_tabbing_mode`].
+    ///
+    /// It is set to [`TabbingMode::Automatic`] by default.
+    fn with_tabbing_mode(self, tabbing_mode: TabbingMode) -> WindowBuilder;
+    /// Centers the window on the given monitor when it's created, see
+    /// [`Window::center_on_monitor`].
+    ///
+    /// This has no effect if a position is set with [`WindowBuilder::with_position`]. By default,
+    /// the window is centered on the main monitor.
+    fn with_centered_on_monitor(self, monitor: MonitorHandle) -> WindowBuilder;
 }
 
 impl WindowBuilderExtMacOS for WindowBuilder {
@@ -163,6 +1349,18 @@ impl WindowBuilderExtMacOS for WindowBuilder {
         self.platform_specific.has_shadow = has_shadow;
         self
     }
+
+    #[inline]
+    fn with_tabbing_mode(mut self, tabbing_mode: TabbingMode) -> WindowBuilder {
+        self.platform_specific.tabbing_mode = tabbing_mode;
+        self
+    }
+
+    #[inline]
+    fn with_centered_on_monitor(mut self, monitor: MonitorHandle) -> WindowBuilder {
+        self.platform_specific.centered_on = Some(monitor.inner);
+        self
+    }
 }
 
 pub trait EventLoopBuilderExtMacOS {
@@ -209,6 +1407,60 @@ pub trait EventLoopBuilderExtMacOS {
     /// # }
     /// ```
     fn with_default_menu(&mut self, enable: bool) -> &mut Self;
+
+    /// Sets whether the application is aware of the display's scale factor.
+    ///
+    /// By default, winit reports the backing scale factor of each window and monitor (`2.0` on
+    /// Retina displays), and all physical sizes and positions are in device pixels.
+    ///
+    /// When set to `false`, the scale factor of every window and monitor is reported as `1.0`,
+    /// so that physical and logical coordinates are the same: [`Window::inner_size`],
+    /// [`Window::outer_position`], [`MonitorHandle::size`], `WindowEvent::Resized`,
+    /// `WindowEvent::Moved` and `WindowEvent::CursorMoved` are all in points, and
+    /// `WindowEvent::ScaleFactorChanged` is never emitted. Applications rendering at
+    /// [`Window::inner_size`] then render at a lower resolution, which the system scales up on
+    /// high-DPI displays. Applications that want to render at device pixels nonetheless can
+    /// scale manually with the backing scale factor of the `NSWindow`.
+    ///
+    /// This applies to all windows of the application.
+    ///
+    /// [`Window::inner_size`]: crate::window::Window::inner_size
+    /// [`Window::outer_position`]: crate::window::Window::outer_position
+    fn with_dpi_aware(&mut self, dpi_aware: bool) -> &mut Self;
+
+    /// Sets whether the application is activated when the event loop starts, i.e. brought to the
+    /// front with its first visible window focused.
+    ///
+    /// When set to `false`, the application doesn't take the focus from the frontmost
+    /// application, e.g. the terminal that it's launched from. Visible windows are still shown,
+    /// but behind the windows of the frontmost application, until the user switches to the
+    /// application or [`Window::focus_window`] is called.
+    ///
+    /// Activation is enabled by default.
+    ///
+    /// [`Window::focus_window`]: crate::window::Window::focus_window
+    fn with_activate_on_launch(&mut self, activate_on_launch: bool) -> &mut Self;
+
+    /// Sets whether the application decides when to quit after the user or the system asks it
+    /// to, e.g. to save its state or prompt the user to save their documents first.
+    ///
+    /// By default, the application quits right away when asked to, e.g. with Cmd-Q, the Quit
+    /// item of the Dock or a logout of the user: [`Event::LoopDestroyed`] is emitted, and the
+    /// process exits.
+    ///
+    /// When set to `true`, [`Event::QuitRequested`] is emitted instead, and quitting is deferred
+    /// until the application calls
+    /// [`EventLoopWindowTargetExtMacOS::reply_to_quit_request`], which it must do eventually:
+    /// with `true` to quit, which then goes on as by default, or with `false` to cancel. Until
+    /// then, the event loop keeps running and the application can show windows and handle input,
+    /// but a logout, restart or shutdown of the system is held up, and is cancelled by the system
+    /// after a while.
+    ///
+    /// Deferring is disabled by default.
+    ///
+    /// [`Event::LoopDestroyed`]: crate::event::Event::LoopDestroyed
+    /// [`Event::QuitRequested`]: crate::event::Event::QuitRequested
+    fn with_deferred_termination(&mut self, deferred_termination: bool) -> &mut Self;
 }
 
 impl<T> EventLoopBuilderExtMacOS for EventLoopBuilder<T> {
@@ -223,6 +1475,24 @@ impl<T> EventLoopBuilderExtMacOS for EventLoopBuilder<T> {
         self.platform_specific.default_menu = enable;
         self
     }
+
+    #[inline]
+    fn with_dpi_aware(&mut self, dpi_aware: bool) -> &mut Self {
+        self.platform_specific.dpi_aware = dpi_aware;
+        self
+    }
+
+    #[inline]
+    fn with_activate_on_launch(&mut self, activate_on_launch: bool) -> &mut Self {
+        self.platform_specific.activate_on_launch = activate_on_launch;
+        self
+    }
+
+    #[inline]
+    fn with_deferred_termination(&mut self, deferred_termination: bool) -> &mut Self {
+        self.platform_specific.deferred_termination = deferred_termination;
+        self
+    }
 }
 
 /// Additional methods on [`MonitorHandle`] that are specific to MacOS.
@@ -231,6 +1501,38 @@ pub trait MonitorHandleExtMacOS {
     fn native_id(&self) -> u32;
     /// Returns a pointer to the NSScreen representing this monitor.
     fn ns_screen(&self) -> Option<*mut c_void>;
+    /// Returns how the monitor is rotated, as set in System Settings.
+    ///
+    /// [`MonitorHandle::size`] and [`MonitorHandle::position`] already take the rotation into
+    /// account: a landscape display rotated by 90° reports a portrait size.
+    fn rotation(&self) -> Rotation;
+    /// Returns the ICC profile of the monitor's color space, as set in System Settings, e.g. to
+    /// render with the monitor's P3 or sRGB gamut.
+    ///
+    /// The profile changes when the user picks another one for the monitor, and windows moved to
+    /// another monitor may need to render with that monitor's profile instead. Returns `None` if
+    /// the monitor has been disconnected.
+    fn icc_profile(&self) -> Option<Vec<u8>>;
+    /// Returns whether the monitor is the built-in display of a laptop, as opposed to an
+    /// external one, e.g. to open windows on an external monitor when one is connected.
+    ///
+    /// Mirroring doesn't affect the result: while the built-in display mirrors an external
+    /// monitor or the other way around, both are still listed by
+    /// [`Window::available_monitors`], and only the built-in display returns `true`.
+    fn is_builtin(&self) -> bool;
+}
+
+/// The clockwise rotation of a monitor, see [`MonitorHandleExtMacOS::rotation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rotation {
+    /// The display isn't rotated.
+    Deg0,
+    /// The display is rotated by 90°.
+    Deg90,
+    /// The display is rotated by 180°.
+    Deg180,
+    /// The display is rotated by 270°.
+    Deg270,
 }
 
 impl MonitorHandleExtMacOS for MonitorHandle {
@@ -242,6 +1544,24 @@ impl MonitorHandleExtMacOS for MonitorHandle {
     fn ns_screen(&self) -> Option<*mut c_void> {
         self.inner.ns_screen().map(|s| s as *mut c_void)
     }
+
+    fn rotation(&self) -> Rotation {
+        // `CGDisplayRotation` only ever returns multiples of 90 degrees.
+        match self.inner.rotation().round() as i32 {
+            90 => Rotation::Deg90,
+            180 => Rotation::Deg180,
+            270 => Rotation::Deg270,
+            _ => Rotation::Deg0,
+        }
+    }
+
+    fn icc_profile(&self) -> Option<Vec<u8>> {
+        self.inner.icc_profile()
+    }
+
+    fn is_builtin(&self) -> bool {
+        self.inner.is_builtin()
+    }
 }
 
 /// Additional methods on [`EventLoopWindowTarget`] that are specific to macOS.
@@ -250,6 +1570,45 @@ pub trait EventLoopWindowTargetExtMacOS {
     fn hide_application(&self);
     /// Hide the other applications. In most applications this is typically triggered with Command+Option-H.
     fn hide_other_applications(&self);
+
+    /// Returns whether windows may be grouped into tabs automatically.
+    ///
+    /// See [`set_allows_automatic_window_tabbing`](Self::set_allows_automatic_window_tabbing).
+    fn allows_automatic_window_tabbing(&self) -> bool;
+
+    /// Sets whether windows may be grouped into tabs automatically, and whether the "Show Tab
+    /// Bar" and related items are added to the "View" and "Window" menus.
+    ///
+    /// This applies to every window of the application, existing or future: it corresponds to
+    /// the `NSWindow.allowsAutomaticWindowTabbing` class property. Pass `false` to get rid of the
+    /// tab bar that otherwise appears when the user's "Prefer tabs" system preference is set to
+    /// "always". Individual windows can still be tabbed with
+    /// [`WindowExtMacOS::set_tabbing_mode`].
+    ///
+    /// Enabled by default.
+    fn set_allows_automatic_window_tabbing(&self, enabled: bool);
+
+    /// Returns the list of monitors, which is kept up to date as monitors are connected,
+    /// disconnected or rearranged, unlike the one returned by
+    /// [`available_monitors`](EventLoopWindowTarget::available_monitors).
+    fn monitor_list(&self) -> MonitorList;
+
+    /// Answers the last [`Event::QuitRequested`], with `true` to quit or `false` to keep running.
+    ///
+    /// See [`EventLoopBuilderExtMacOS::with_deferred_termination`]. Does nothing if there's no
+    /// request to answer.
+    ///
+    /// [`Event::QuitRequested`]: crate::event::Event::QuitRequested
+    fn reply_to_quit_request(&self, quit: bool);
+
+    /// Returns the accent color chosen in System Settings, as `(red, green, blue, alpha)` in the
+    /// sRGB color space, with each component ranging from `0.0` to `1.0`.
+    ///
+    /// [`Event::AccentColorChanged`] is emitted when it changes. Before macOS 10.14, which has no
+    /// accent color, this is the highlight color instead.
+    ///
+    /// [`Event::AccentColorChanged`]: crate::event::Event::AccentColorChanged
+    fn system_accent_color(&self) -> (f64, f64, f64, f64);
 }
 
 impl<T> EventLoopWindowTargetExtMacOS for EventLoopWindowTarget<T> {
@@ -260,4 +1619,135 @@ impl<T> EventLoopWindowTargetExtMacOS for EventLoopWindowTarget<T> {
     fn hide_other_applications(&self) {
         self.p.hide_other_applications()
     }
+
+    fn allows_automatic_window_tabbing(&self) -> bool {
+        self.p.allows_automatic_window_tabbing()
+    }
+
+    fn set_allows_automatic_window_tabbing(&self, enabled: bool) {
+        self.p.set_allows_automatic_window_tabbing(enabled)
+    }
+
+    fn monitor_list(&self) -> MonitorList {
+        MonitorList {
+            inner: self.p.monitor_list(),
+        }
+    }
+
+    fn reply_to_quit_request(&self, quit: bool) {
+        self.p.reply_to_quit_request(quit)
+    }
+
+    fn system_accent_color(&self) -> (f64, f64, f64, f64) {
+        self.p.system_accent_color()
+    }
+}
+
+/// The insets of the safe area of a window's content in physical pixels, see
+/// [`WindowExtMacOS::safe_area_insets`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SafeAreaInsets {
+    pub top: u32,
+    pub left: u32,
+    pub bottom: u32,
+    pub right: u32,
+}
+
+/// The monitors of the system, kept up to date as monitors are connected, disconnected or
+/// rearranged, see [`EventLoopWindowTargetExtMacOS::monitor_list`].
+///
+/// The list is shared by the whole application. The monitors that stay connected keep their order
+/// in the list, and newly connected ones are appended to it, so the index of a monitor only
+/// changes when a monitor before it is disconnected. A [`MonitorHandle`] compares equal to the
+/// handle of the same monitor obtained before or after a change, and so can be used as a key for
+/// per-monitor state, or be looked up in the list again with [`contains`](Self::contains).
+///
+/// The list doesn't signal changes by itself: compare [`generation`](Self::generation) with its
+/// previous value, e.g. on [`Event::MainEventsCleared`](crate::event::Event::MainEventsCleared),
+/// to find out whether the monitors changed.
+#[derive(Debug, Clone, Copy)]
+pub struct MonitorList {
+    inner: &'static crate::platform_impl::MonitorList,
+}
+
+impl MonitorList {
+    /// Returns the monitors currently in the list.
+    pub fn monitors(&self) -> Vec<MonitorHandle> {
+        self.inner
+            .monitors()
+            .into_iter()
+            .map(|inner| MonitorHandle { inner })
+            .collect()
+    }
+
+    /// Returns the number of monitors in the list.
+    pub fn len(&self) -> usize {
+        self.inner.monitors().len()
+    }
+
+    /// Returns whether the list is empty, which only happens while no monitor is connected.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the monitor at `index`, if there's one.
+    pub fn get(&self, index: usize) -> Option<MonitorHandle> {
+        self.inner.monitors().get(index).map(|inner| MonitorHandle {
+            inner: inner.clone(),
+        })
+    }
+
+    /// Returns whether `monitor` is still connected.
+    pub fn contains(&self, monitor: &MonitorHandle) -> bool {
+        self.inner.monitors().contains(&monitor.inner)
+    }
+
+    /// Returns a counter that increases whenever the monitors are reconfigured, i.e. when a
+    /// monitor is connected or disconnected, or when the arrangement, the resolution or the
+    /// mirroring of the monitors changes.
+    pub fn generation(&self) -> u64 {
+        self.inner.generation()
+    }
+}
+
+/// Returns the key at the position of `scancode` on an ANSI (US) keyboard, regardless of the
+/// active keyboard layout.
+///
+/// The [`virtual_keycode`] of [`KeyboardInput`] follows the layout, so that e.g. the key
+/// labelled "Z" on an AZERTY keyboard reports [`VirtualKeyCode::Z`]. Use this to bind keys by
+/// their position instead, such as W-A-S-D movement controls, which are reported as
+/// [`VirtualKeyCode::W`], [`VirtualKeyCode::A`], [`VirtualKeyCode::S`] and [`VirtualKeyCode::D`]
+/// on every layout.
+///
+/// Returns `None` for keys without a counterpart in [`VirtualKeyCode`].
+///
+/// [`virtual_keycode`]: crate::event::KeyboardInput::virtual_keycode
+/// [`KeyboardInput`]: crate::event::KeyboardInput
+pub fn scancode_to_physical_key(scancode: ScanCode) -> Option<VirtualKeyCode> {
+    u16::try_from(scancode)
+        .ok()
+        .and_then(crate::platform_impl::scancode_to_keycode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scancode_to_physical_key_ignores_the_layout() {
+        // `kVK_ANSI_W`, `kVK_ANSI_A`, `kVK_ANSI_S` and `kVK_ANSI_D`.
+        assert_eq!(scancode_to_physical_key(0x0d), Some(VirtualKeyCode::W));
+        assert_eq!(scancode_to_physical_key(0x00), Some(VirtualKeyCode::A));
+        assert_eq!(scancode_to_physical_key(0x01), Some(VirtualKeyCode::S));
+        assert_eq!(scancode_to_physical_key(0x02), Some(VirtualKeyCode::D));
+        // `kVK_ANSI_Period`.
+        assert_eq!(scancode_to_physical_key(0x2f), Some(VirtualKeyCode::Period));
+    }
+
+    #[test]
+    fn scancode_to_physical_key_of_unknown_keys() {
+        // `kVK_JIS_Eisu`, which has no `VirtualKeyCode`.
+        assert_eq!(scancode_to_physical_key(0x66), None);
+        assert_eq!(scancode_to_physical_key(0x1_0000), None);
+    }
 }
@@ -741,6 +741,10 @@ impl Window {
         // no effect
     }
 
+    pub fn center_on_monitor(&self, _monitor: &monitor::MonitorHandle) {
+        // no effect
+    }
+
     pub fn inner_size(&self) -> PhysicalSize<u32> {
         self.outer_size()
     }
@@ -753,6 +757,14 @@ impl Window {
         MonitorHandle.size()
     }
 
+    pub fn inner_size_f64(&self) -> PhysicalSize<f64> {
+        self.inner_size().cast()
+    }
+
+    pub fn outer_size_f64(&self) -> PhysicalSize<f64> {
+        self.outer_size().cast()
+    }
+
     pub fn set_min_inner_size(&self, _: Option<Size>) {}
 
     pub fn set_max_inner_size(&self, _: Option<Size>) {}
@@ -807,7 +819,9 @@ impl Window {
 
     pub fn set_ime_allowed(&self, _allowed: bool) {}
 
-    pub fn focus_window(&self) {}
+    pub fn focus_window(&self) -> bool {
+        false
+    }
 
     pub fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
 
@@ -819,7 +833,10 @@ impl Window {
         ))
     }
 
-    pub fn set_cursor_grab(&self, _: CursorGrabMode) -> Result<(), error::ExternalError> {
+    pub fn set_cursor_grab(
+        &self,
+        _: CursorGrabMode,
+    ) -> Result<CursorGrabMode, error::ExternalError> {
         Err(error::ExternalError::NotSupported(
             error::NotSupportedError::new(),
         ))
@@ -839,6 +856,12 @@ impl Window {
         ))
     }
 
+    pub fn set_content_protected(&self, _protected: bool) {}
+
+    pub fn is_content_protected(&self) -> bool {
+        false
+    }
+
     pub fn raw_window_handle(&self) -> RawWindowHandle {
         if let Some(native_window) = ndk_glue::native_window() {
             native_window.raw_window_handle()
@@ -101,6 +101,10 @@ impl Inner {
         }
     }
 
+    pub fn center_on_monitor(&self, _monitor: &RootMonitorHandle) {
+        warn!("`Window::center_on_monitor` is ignored on iOS")
+    }
+
     pub fn set_outer_position(&self, physical_position: Position) {
         unsafe {
             let scale_factor = self.scale_factor();
@@ -142,6 +146,14 @@ impl Inner {
         }
     }
 
+    pub fn inner_size_f64(&self) -> PhysicalSize<f64> {
+        self.inner_size().cast()
+    }
+
+    pub fn outer_size_f64(&self) -> PhysicalSize<f64> {
+        self.outer_size().cast()
+    }
+
     pub fn set_inner_size(&self, _size: Size) {
         warn!("not clear what `Window::set_inner_size` means on iOS");
     }
@@ -187,7 +199,7 @@ impl Inner {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
-    pub fn set_cursor_grab(&self, _: CursorGrabMode) -> Result<(), ExternalError> {
+    pub fn set_cursor_grab(&self, _: CursorGrabMode) -> Result<CursorGrabMode, ExternalError> {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
@@ -203,6 +215,14 @@ impl Inner {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    pub fn set_content_protected(&self, _protected: bool) {
+        warn!("`Window::set_content_protected` is ignored on iOS")
+    }
+
+    pub fn is_content_protected(&self) -> bool {
+        false
+    }
+
     pub fn set_minimized(&self, _minimized: bool) {
         warn!("`Window::set_minimized` is ignored on iOS")
     }
@@ -298,8 +318,9 @@ impl Inner {
         warn!("`Window::set_ime_allowed` is ignored on iOS")
     }
 
-    pub fn focus_window(&self) {
-        warn!("`Window::set_focus` is ignored on iOS")
+    pub fn focus_window(&self) -> bool {
+        warn!("`Window::set_focus` is ignored on iOS");
+        false
     }
 
     pub fn request_user_attention(&self, _request_type: Option<UserAttentionType>) {
@@ -200,7 +200,7 @@ impl DeviceId {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum MonitorHandle {
     #[cfg(feature = "x11")]
     X(x11::MonitorHandle),
@@ -362,6 +362,16 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_outer_position(position))
     }
 
+    #[inline]
+    pub fn center_on_monitor(&self, monitor: &RootMonitorHandle) {
+        match self {
+            #[cfg(feature = "x11")]
+            Window::X(ref w) => w.center_on_monitor(monitor),
+            #[cfg(feature = "wayland")]
+            Window::Wayland(_) => (),
+        }
+    }
+
     #[inline]
     pub fn inner_size(&self) -> PhysicalSize<u32> {
         x11_or_wayland!(match self; Window(w) => w.inner_size())
@@ -372,6 +382,17 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.outer_size())
     }
 
+    // Both X11 and Wayland sizes are whole pixels.
+    #[inline]
+    pub fn inner_size_f64(&self) -> PhysicalSize<f64> {
+        self.inner_size().cast()
+    }
+
+    #[inline]
+    pub fn outer_size_f64(&self) -> PhysicalSize<f64> {
+        self.outer_size().cast()
+    }
+
     #[inline]
     pub fn set_inner_size(&self, size: Size) {
         x11_or_wayland!(match self; Window(w) => w.set_inner_size(size))
@@ -413,7 +434,7 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<CursorGrabMode, ExternalError> {
         x11_or_wayland!(match self; Window(window) => window.set_cursor_grab(mode))
     }
 
@@ -432,6 +453,14 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_cursor_hittest(hittest))
     }
 
+    #[inline]
+    pub fn set_content_protected(&self, _protected: bool) {}
+
+    #[inline]
+    pub fn is_content_protected(&self) -> bool {
+        false
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         x11_or_wayland!(match self; Window(w) => w.scale_factor() as f64)
@@ -508,12 +537,12 @@ impl Window {
     }
 
     #[inline]
-    pub fn focus_window(&self) {
+    pub fn focus_window(&self) -> bool {
         match self {
             #[cfg(feature = "x11")]
             Window::X(ref w) => w.focus_window(),
             #[cfg(feature = "wayland")]
-            Window::Wayland(_) => (),
+            Window::Wayland(_) => false,
         }
     }
     pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
@@ -515,19 +515,19 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<CursorGrabMode, ExternalError> {
         if !self.windowing_features.pointer_constraints() {
             if mode == CursorGrabMode::None {
-                return Ok(());
+                return Ok(CursorGrabMode::None);
             }
 
             return Err(ExternalError::NotSupported(NotSupportedError::new()));
         }
 
-        *self.cursor_grab_mode.lock().unwrap() = mode;
+        let previous_mode = std::mem::replace(&mut *self.cursor_grab_mode.lock().unwrap(), mode);
         self.send_request(WindowRequest::SetCursorGrabMode(mode));
 
-        Ok(())
+        Ok(previous_mode)
     }
 
     pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
@@ -1082,6 +1082,12 @@ impl UnownedWindow {
         self.set_position_physical(x, y);
     }
 
+    #[inline]
+    pub fn center_on_monitor(&self, monitor: &RootMonitorHandle) {
+        let (x, y) = crate::window::centered_on_monitor(monitor, self.outer_size()).into();
+        self.set_position_physical(x, y);
+    }
+
     pub(crate) fn inner_size_physical(&self) -> (u32, u32) {
         // This should be okay to unwrap since the only error XGetGeometry can return
         // is BadWindow, and if the window handle is bad we have bigger problems.
@@ -1296,10 +1302,11 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<CursorGrabMode, ExternalError> {
         let mut grabbed_lock = self.cursor_grabbed_mode.lock().unwrap();
-        if mode == *grabbed_lock {
-            return Ok(());
+        let previous_mode = *grabbed_lock;
+        if mode == previous_mode {
+            return Ok(previous_mode);
         }
 
         unsafe {
@@ -1365,7 +1372,7 @@ impl UnownedWindow {
             *grabbed_lock = mode;
         }
 
-        result
+        result.map(|()| previous_mode)
     }
 
     #[inline]
@@ -1470,7 +1477,7 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn focus_window(&self) {
+    pub fn focus_window(&self) -> bool {
         let state_atom = unsafe { self.xconn.get_atom_unchecked(b"WM_STATE\0") };
         let state_type_atom = unsafe { self.xconn.get_atom_unchecked(b"CARD32\0") };
         let is_minimized = if let Ok(state) =
@@ -1500,7 +1507,11 @@ impl UnownedWindow {
                     "`flush` returned an error when focusing the window. Error was: {}",
                     e
                 );
+                return false;
             }
+            true
+        } else {
+            false
         }
     }
 
@@ -1,15 +1,42 @@
-use std::collections::VecDeque;
+use std::{cell::Cell, collections::VecDeque};
 
 use cocoa::{
-    appkit::{self, NSEvent},
-    base::id,
+    appkit::{self, NSApp, NSEvent},
+    base::{id, nil},
 };
 use objc2::foundation::NSObject;
 use objc2::{declare_class, ClassType};
 
 use super::appkit::{NSApplication, NSResponder};
-use super::{app_state::AppState, event::EventWrapper, util, DEVICE_ID};
-use crate::event::{DeviceEvent, ElementState, Event};
+use super::{app_state::AppState, event::EventWrapper, util, window_delegate, DEVICE_ID};
+use crate::{
+    event::{DeviceEvent, ElementState, Event},
+    event_loop::DeviceEventFilter,
+};
+
+thread_local! {
+    // Only accessed from the main thread, like `sendEvent:`.
+    static DEVICE_EVENT_FILTER: Cell<DeviceEventFilter> = Cell::new(Default::default());
+}
+
+pub(super) fn set_device_event_filter(filter: DeviceEventFilter) {
+    DEVICE_EVENT_FILTER.with(|f| f.set(filter));
+}
+
+/// Returns whether device events pass the filter set by `set_device_event_filter`.
+///
+/// The application only receives the input that's meant for it, so device
+/// events are never reported for the input of other applications.
+pub(super) fn device_events_allowed() -> bool {
+    match DEVICE_EVENT_FILTER.with(Cell::get) {
+        DeviceEventFilter::Always => false,
+        DeviceEventFilter::Unfocused => unsafe {
+            let key_window: id = msg_send![NSApp(), keyWindow];
+            key_window != nil
+        },
+        DeviceEventFilter::Never => true,
+    }
+}
 
 declare_class!(
     #[derive(Debug, PartialEq, Eq, Hash)]
@@ -43,6 +70,11 @@ declare_class!(
                 } else {
                     maybe_dispatch_device_event(event);
                     let _: () = msg_send![super(self), sendEvent: event];
+                    // Moving a window by dragging it doesn't report when the
+                    // user stops, so look for the mouse up that ends the drag.
+                    if event_type == appkit::NSLeftMouseUp {
+                        window_delegate::end_user_moves();
+                    }
                 }
             }
         }
@@ -50,6 +82,9 @@ declare_class!(
 );
 
 unsafe fn maybe_dispatch_device_event(event: id) {
+    if !device_events_allowed() {
+        return;
+    }
     let event_type = event.eventType();
     match event_type {
         appkit::NSMouseMoved
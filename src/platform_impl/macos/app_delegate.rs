@@ -1,16 +1,34 @@
-use cocoa::appkit::NSApplicationActivationPolicy;
-use objc2::foundation::NSObject;
+use std::path::PathBuf;
+
+use cocoa::{
+    appkit::{NSApp, NSApplicationActivationPolicy},
+    base::{id, nil},
+    foundation::NSFastEnumeration,
+};
+use objc2::foundation::{NSObject, NSUInteger};
 use objc2::rc::{Id, Shared};
 use objc2::runtime::Object;
 use objc2::{declare_class, ClassType};
 
-use super::app_state::AppState;
+use super::{app_state::AppState, event::EventWrapper, util};
+use crate::event::Event;
+
+/// `NSTerminateNow` from `NSApplicationTerminateReply`.
+const NS_TERMINATE_NOW: NSUInteger = 1;
+
+/// `NSTerminateLater` from `NSApplicationTerminateReply`.
+const NS_TERMINATE_LATER: NSUInteger = 2;
+
+/// `NSApplicationDelegateReplySuccess` from `NSApplicationDelegateReply`.
+const NS_APPLICATION_DELEGATE_REPLY_SUCCESS: NSUInteger = 0;
 
 declare_class!(
     #[derive(Debug)]
     pub(super) struct ApplicationDelegate {
         activation_policy: NSApplicationActivationPolicy,
         default_menu: bool,
+        activate_on_launch: bool,
+        deferred_termination: bool,
     }
 
     unsafe impl ClassType for ApplicationDelegate {
@@ -19,24 +37,95 @@ declare_class!(
     }
 
     unsafe impl ApplicationDelegate {
-        #[sel(initWithActivationPolicy:defaultMenu:)]
+        #[sel(initWithActivationPolicy:defaultMenu:activateOnLaunch:deferredTermination:)]
         fn init(
             &mut self,
             activation_policy: NSApplicationActivationPolicy,
             default_menu: bool,
+            activate_on_launch: bool,
+            deferred_termination: bool,
         ) -> Option<&mut Self> {
             let this: Option<&mut Self> = unsafe { msg_send![super(self), init] };
             this.map(|this| {
                 *this.activation_policy = activation_policy;
                 *this.default_menu = default_menu;
+                *this.activate_on_launch = activate_on_launch;
+                *this.deferred_termination = deferred_termination;
+                unsafe {
+                    let center: id =
+                        msg_send![class!(NSDistributedNotificationCenter), defaultCenter];
+                    let name = util::ns_string_id_ref("AppleColorPreferencesChangedNotification");
+                    let _: () = msg_send![
+                        center,
+                        addObserver: &*this
+                        selector: sel!(colorPreferencesDidChange:)
+                        name: *name
+                        object: nil
+                    ];
+                }
                 this
             })
         }
 
+        #[sel(dealloc)]
+        fn dealloc(&mut self) {
+            unsafe {
+                let center: id = msg_send![class!(NSDistributedNotificationCenter), defaultCenter];
+                let _: () = msg_send![center, removeObserver: &*self];
+                let _: () = msg_send![super(self), dealloc];
+            }
+        }
+
         #[sel(applicationDidFinishLaunching:)]
         fn did_finish_launching(&self, _sender: *const Object) {
             trace_scope!("applicationDidFinishLaunching:");
-            AppState::launched(*self.activation_policy, *self.default_menu);
+            AppState::launched(
+                *self.activation_policy,
+                *self.default_menu,
+                *self.activate_on_launch,
+            );
+        }
+
+        #[sel(applicationShouldTerminate:)]
+        fn should_terminate(&self, _sender: *const Object) -> NSUInteger {
+            trace_scope!("applicationShouldTerminate:");
+            if !*self.deferred_termination {
+                // `applicationWillTerminate:` delivers `LoopDestroyed` next.
+                return NS_TERMINATE_NOW;
+            }
+            // The application replies with `reply_to_quit_request` once it has
+            // handled `Event::QuitRequested`. In the meantime, AppKit keeps
+            // running the event loop in `NSModalPanelRunLoopMode`, which the
+            // observers are also registered in.
+            AppState::queue_event(EventWrapper::StaticEvent(Event::QuitRequested));
+            NS_TERMINATE_LATER
+        }
+
+        // Posted when the accent or the highlight color is changed in System
+        // Settings.
+        #[sel(colorPreferencesDidChange:)]
+        fn color_preferences_did_change(&self, _notification: id) {
+            trace_scope!("colorPreferencesDidChange:");
+            let color = unsafe { util::system_accent_color() };
+            AppState::queue_event(EventWrapper::StaticEvent(Event::AccentColorChanged(color)));
+        }
+
+        // Files that the application is launched to open are passed here before
+        // `applicationDidFinishLaunching:`, so the event stays queued until after
+        // `StartCause::Init`.
+        #[sel(application:openFiles:)]
+        fn open_files(&self, _sender: *const Object, filenames: id) {
+            trace_scope!("application:openFiles:");
+            let paths = unsafe { filenames.iter() }
+                .map(|filename| PathBuf::from(unsafe { util::id_to_string_lossy(filename) }))
+                .collect();
+            AppState::queue_event(EventWrapper::StaticEvent(Event::OpenFiles(paths)));
+            unsafe {
+                let _: () = msg_send![
+                    NSApp(),
+                    replyToOpenOrPrint: NS_APPLICATION_DELEGATE_REPLY_SUCCESS
+                ];
+            }
         }
 
         #[sel(applicationWillTerminate:)]
@@ -52,12 +141,16 @@ impl ApplicationDelegate {
     pub(super) fn new(
         activation_policy: NSApplicationActivationPolicy,
         default_menu: bool,
+        activate_on_launch: bool,
+        deferred_termination: bool,
     ) -> Id<Self, Shared> {
         unsafe {
             msg_send_id![
                 msg_send_id![Self::class(), alloc],
                 initWithActivationPolicy: activation_policy,
                 defaultMenu: default_menu,
+                activateOnLaunch: activate_on_launch,
+                deferredTermination: deferred_termination,
             ]
         }
     }
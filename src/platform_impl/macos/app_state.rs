@@ -185,10 +185,45 @@ impl Handler {
         mem::take(&mut *self.events())
     }
 
+    /// Takes the queued `Resized` and `ScaleFactorChanged` events of a window,
+    /// leaving the other events queued.
+    fn take_resize_events(&self, window_id: WindowId) -> VecDeque<EventWrapper> {
+        let mut events = self.events();
+        let (resize_events, other_events) =
+            mem::take(&mut *events)
+                .into_iter()
+                .partition(|wrapper| match wrapper {
+                    EventWrapper::StaticEvent(Event::WindowEvent {
+                        window_id: id,
+                        event: WindowEvent::Resized(_),
+                    }) => *id == window_id,
+                    EventWrapper::EventProxy(EventProxy::DpiChangedProxy { ns_window, .. }) => {
+                        WindowId(get_window_id(**ns_window)) == window_id
+                    }
+                    _ => false,
+                });
+        *events = other_events;
+        resize_events
+    }
+
     fn should_redraw(&self) -> Vec<WindowId> {
         mem::take(&mut *self.redraw())
     }
 
+    fn request_redraw(&self, window_id: WindowId) {
+        let mut pending_redraw = self.redraw();
+        if !pending_redraw.contains(&window_id) {
+            pending_redraw.push(window_id);
+        }
+    }
+
+    /// Whether redraws were requested since the last `should_redraw`, e.g.
+    /// while handling `RedrawRequested`, in which case the run loop mustn't go
+    /// to sleep before delivering them.
+    fn has_pending_redraws(&self) -> bool {
+        !self.redraw().is_empty()
+    }
+
     fn get_in_callback(&self) -> bool {
         self.in_callback.load(Ordering::Acquire)
     }
@@ -277,7 +312,11 @@ impl AppState {
         }
     }
 
-    pub fn launched(activation_policy: NSApplicationActivationPolicy, create_default_menu: bool) {
+    pub fn launched(
+        activation_policy: NSApplicationActivationPolicy,
+        create_default_menu: bool,
+        activate: bool,
+    ) {
         unsafe {
             let ns_app = NSApp();
 
@@ -287,8 +326,11 @@ impl AppState {
             ns_app.setActivationPolicy_(activation_policy);
 
             window_activation_hack(ns_app);
-            // TODO: Consider allowing the user to specify they don't want their application activated
-            ns_app.activateIgnoringOtherApps_(Bool::YES.as_raw());
+            // Without activation, the windows are still shown, but behind the
+            // windows of the frontmost application, which keeps the focus.
+            if activate {
+                ns_app.activateIgnoringOtherApps_(Bool::YES.as_raw());
+            }
         };
         HANDLER.set_ready();
         HANDLER.waker().start();
@@ -345,10 +387,7 @@ impl AppState {
 
     // This is called from multiple threads at present
     pub fn queue_redraw(window_id: WindowId) {
-        let mut pending_redraw = HANDLER.redraw();
-        if !pending_redraw.contains(&window_id) {
-            pending_redraw.push(window_id);
-        }
+        HANDLER.request_redraw(window_id);
         unsafe {
             let rl = CFRunLoopGetMain();
             CFRunLoopWakeUp(rl);
@@ -356,7 +395,32 @@ impl AppState {
     }
 
     pub fn handle_redraw(window_id: WindowId) {
+        // `drawRect:` may be called from within the event handler, e.g. when
+        // it resizes the window, in which case the handler can't be called
+        // again, so the redraw is delivered with the next ones instead.
+        if !HANDLER.is_ready() || HANDLER.get_in_callback() {
+            Self::queue_redraw(window_id);
+            return;
+        }
+        HANDLER.set_in_callback(true);
+        // `drawRect:` is also called outside of `cleared`, e.g. during a live
+        // resize, in which case the `Resized` event for the new size is still
+        // queued. Deliver it first, so that the redraw doesn't precede it, but
+        // leave the other events for `cleared`, so that they're still
+        // delivered between `NewEvents` and `MainEventsCleared`.
+        for event in HANDLER.take_resize_events(window_id) {
+            HANDLER.handle_nonuser_event(event);
+        }
         HANDLER.handle_nonuser_event(EventWrapper::StaticEvent(Event::RedrawRequested(window_id)));
+        HANDLER.set_in_callback(false);
+    }
+
+    /// Delivers the `Resized` event of a window and a redraw right away, see
+    /// `set_synchronous_resize`. When the resize is caused by the event
+    /// handler itself, e.g. with `set_inner_size`, `handle_redraw` requests
+    /// the redraw instead of calling the handler again.
+    pub fn handle_synchronous_resize(window_id: WindowId) {
+        Self::handle_redraw(window_id);
     }
 
     pub fn queue_event(wrapper: EventWrapper) {
@@ -396,6 +460,14 @@ impl AppState {
         HANDLER.handle_nonuser_event(EventWrapper::StaticEvent(Event::RedrawEventsCleared));
         HANDLER.set_in_callback(false);
 
+        // Redraws requested while handling `RedrawRequested` are delivered on
+        // the next iteration, so the run loop mustn't go to sleep until then.
+        // The wake up from `queue_redraw` may be lost as the run loop is about
+        // to wait, which would stall the redraws until the next input event.
+        if HANDLER.has_pending_redraws() {
+            unsafe { CFRunLoopWakeUp(CFRunLoopGetMain()) };
+        }
+
         if HANDLER.should_exit() {
             unsafe {
                 let app: id = NSApp();
@@ -448,3 +520,44 @@ unsafe fn window_activation_hack(ns_app: id) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window_id(id: usize) -> WindowId {
+        WindowId(crate::platform_impl::WindowId(id))
+    }
+
+    #[test]
+    fn redraws_are_requested_once_per_window() {
+        let handler = Handler::default();
+        handler.request_redraw(window_id(1));
+        handler.request_redraw(window_id(2));
+        handler.request_redraw(window_id(1));
+        assert_eq!(handler.should_redraw(), [window_id(1), window_id(2)]);
+        assert!(!handler.has_pending_redraws());
+    }
+
+    #[test]
+    fn redraws_requested_while_redrawing_keep_the_run_loop_awake() {
+        let handler = Handler::default();
+        handler.request_redraw(window_id(1));
+
+        // Each `RedrawRequested` requests the next one, as an application
+        // animating continuously does.
+        let mut redraws = 0;
+        for _ in 0..100 {
+            for window_id in handler.should_redraw() {
+                redraws += 1;
+                handler.request_redraw(window_id);
+            }
+            assert!(handler.has_pending_redraws());
+        }
+        assert_eq!(redraws, 100);
+
+        // Once they stop being requested, the run loop may sleep.
+        handler.should_redraw();
+        assert!(!handler.has_pending_redraws());
+    }
+}
@@ -41,8 +41,8 @@ macro_rules! def_undocumented_cursor {
         pub fn $name:ident();
     )*} => {$(
         $(#[$($m)*])*
-        pub fn $name() -> Id<Self, Shared> {
-            unsafe { Self::from_selector(sel!($name)).unwrap_or_else(|| Default::default()) }
+        pub fn $name() -> Option<Id<Self, Shared>> {
+            unsafe { Self::from_selector(sel!($name)) }
         }
     )*};
 }
@@ -99,6 +99,16 @@ extern_methods!(
     }
 
     /// Undocumented cursors
+    unsafe impl NSCursor {
+        /// Hides the cursor until `unhide` is called. Calls are counted, so each
+        /// call must be balanced by a call to `unhide`.
+        #[sel(hide)]
+        pub fn hide();
+
+        #[sel(unhide)]
+        pub fn unhide();
+    }
+
     unsafe impl NSCursor {
         #[sel(respondsToSelector:)]
         fn class_responds_to(sel: Sel) -> bool;
@@ -171,7 +181,7 @@ extern_methods!(
                 }
             }
             let mut y = 0.0;
-            if let Some(n) = info.get(&*ns_string!("hotx")) {
+            if let Some(n) = info.get(&*ns_string!("hoty")) {
                 if n.is_kind_of::<NSNumber>() {
                     let ptr: *const NSObject = n;
                     let ptr: *const NSNumber = ptr.cast();
@@ -212,24 +222,66 @@ impl NSCursor {
             CursorIcon::NResize => Self::resizeUpCursor(),
             CursorIcon::WResize => Self::resizeLeftCursor(),
             CursorIcon::SResize => Self::resizeDownCursor(),
+            // The system has no separate column and row resize cursors, the
+            // ones for resizing split views are the same as these.
             CursorIcon::EwResize | CursorIcon::ColResize => Self::resizeLeftRightCursor(),
             CursorIcon::NsResize | CursorIcon::RowResize => Self::resizeUpDownCursor(),
-            CursorIcon::Help => Self::_helpCursor(),
-            CursorIcon::ZoomIn => Self::_zoomInCursor(),
-            CursorIcon::ZoomOut => Self::_zoomOutCursor(),
-            CursorIcon::NeResize => Self::_windowResizeNorthEastCursor(),
-            CursorIcon::NwResize => Self::_windowResizeNorthWestCursor(),
-            CursorIcon::SeResize => Self::_windowResizeSouthEastCursor(),
-            CursorIcon::SwResize => Self::_windowResizeSouthWestCursor(),
-            CursorIcon::NeswResize => Self::_windowResizeNorthEastSouthWestCursor(),
-            CursorIcon::NwseResize => Self::_windowResizeNorthWestSouthEastCursor(),
+            // The undocumented cursors fall back to the closest documented
+            // one if they're missing, see `is_supported`.
+            CursorIcon::Help => Self::_helpCursor().unwrap_or_else(Self::arrowCursor),
+            CursorIcon::ZoomIn => Self::_zoomInCursor().unwrap_or_else(Self::arrowCursor),
+            CursorIcon::ZoomOut => Self::_zoomOutCursor().unwrap_or_else(Self::arrowCursor),
+            CursorIcon::NeResize => Self::_windowResizeNorthEastCursor()
+                .or_else(Self::_windowResizeNorthEastSouthWestCursor)
+                .unwrap_or_else(Self::moveCursor),
+            CursorIcon::NwResize => Self::_windowResizeNorthWestCursor()
+                .or_else(Self::_windowResizeNorthWestSouthEastCursor)
+                .unwrap_or_else(Self::moveCursor),
+            CursorIcon::SeResize => Self::_windowResizeSouthEastCursor()
+                .or_else(Self::_windowResizeNorthWestSouthEastCursor)
+                .unwrap_or_else(Self::moveCursor),
+            CursorIcon::SwResize => Self::_windowResizeSouthWestCursor()
+                .or_else(Self::_windowResizeNorthEastSouthWestCursor)
+                .unwrap_or_else(Self::moveCursor),
+            CursorIcon::NeswResize => {
+                Self::_windowResizeNorthEastSouthWestCursor().unwrap_or_else(Self::moveCursor)
+            }
+            CursorIcon::NwseResize => {
+                Self::_windowResizeNorthWestSouthEastCursor().unwrap_or_else(Self::moveCursor)
+            }
             // This is the wrong semantics for `Wait`, but it's the same as
-            // what's used in Safari and Chrome.
-            CursorIcon::Wait | CursorIcon::Progress => Self::busyButClickableCursor(),
+            // what's used in Safari and Chrome. The window server animates
+            // this cursor on its own, unlike one loaded from its frames with
+            // `load_webkit_cursor`, and the beach ball of `_waitCursor` is
+            // only meant for unresponsive applications.
+            CursorIcon::Wait | CursorIcon::Progress => {
+                Self::busyButClickableCursor().unwrap_or_else(Self::arrowCursor)
+            }
+            // Like in Safari, panning in all directions uses the move cursor,
+            // as there's no dedicated panning cursor.
             CursorIcon::Move | CursorIcon::AllScroll => Self::moveCursor(),
             CursorIcon::Cell => Self::cellCursor(),
         }
     }
+
+    /// Whether the cursor for `icon` is available, rather than replaced by a
+    /// fallback in `from_icon`.
+    pub fn is_supported(icon: CursorIcon) -> bool {
+        let sel = match icon {
+            CursorIcon::Help => sel!(_helpCursor),
+            CursorIcon::ZoomIn => sel!(_zoomInCursor),
+            CursorIcon::ZoomOut => sel!(_zoomOutCursor),
+            CursorIcon::NeResize => sel!(_windowResizeNorthEastCursor),
+            CursorIcon::NwResize => sel!(_windowResizeNorthWestCursor),
+            CursorIcon::SeResize => sel!(_windowResizeSouthEastCursor),
+            CursorIcon::SwResize => sel!(_windowResizeSouthWestCursor),
+            CursorIcon::NeswResize => sel!(_windowResizeNorthEastSouthWestCursor),
+            CursorIcon::NwseResize => sel!(_windowResizeNorthWestSouthEastCursor),
+            CursorIcon::Wait | CursorIcon::Progress => sel!(busyButClickableCursor),
+            _ => return true,
+        };
+        Self::class_responds_to(sel)
+    }
 }
 
 impl DefaultId for NSCursor {
@@ -18,6 +18,9 @@ extern_methods!(
     unsafe impl NSView {
         #[sel(bounds)]
         pub fn bounds(&self) -> NSRect;
+
+        #[sel(frame)]
+        pub fn frame(&self) -> NSRect;
     }
 
     unsafe impl NSView {
@@ -0,0 +1,105 @@
+use std::{os::raw::c_void, ptr, sync::Mutex};
+
+use core_graphics::display::CGDirectDisplayID;
+
+use super::{app_state::AppState, ffi};
+use crate::window::WindowId;
+
+struct Context {
+    link: ffi::CVDisplayLinkRef,
+    window_id: WindowId,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    running: bool,
+    redraw_requested: bool,
+}
+
+/// Delivers `RedrawRequested` for a window in sync with the refresh of the
+/// display that the window is on.
+///
+/// The display link is started by `request_redraw`, and stops itself on the
+/// first refresh without a pending redraw, so that it doesn't wake up the
+/// application on every refresh while nothing is drawn.
+pub struct DisplayLink {
+    link: ffi::CVDisplayLinkRef,
+    context: *mut Context,
+}
+
+// The display link and its context are thread-safe.
+unsafe impl Send for DisplayLink {}
+
+impl DisplayLink {
+    pub fn new(window_id: WindowId, display_id: CGDirectDisplayID) -> Option<Self> {
+        let mut link = ptr::null_mut();
+        if unsafe { ffi::CVDisplayLinkCreateWithCGDisplay(display_id, &mut link) }
+            != ffi::kCVReturnSuccess
+        {
+            return None;
+        }
+        let context = Box::into_raw(Box::new(Context {
+            link,
+            window_id,
+            state: Default::default(),
+        }));
+        unsafe {
+            ffi::CVDisplayLinkSetOutputCallback(link, output_callback, context as *mut c_void)
+        };
+        Some(DisplayLink { link, context })
+    }
+
+    /// Requests a redraw on the next refresh of the display, starting the
+    /// display link if it's stopped.
+    pub fn request_redraw(&self) {
+        let mut state = unsafe { &*self.context }.state.lock().unwrap();
+        state.redraw_requested = true;
+        if !state.running {
+            state.running = true;
+            unsafe { ffi::CVDisplayLinkStart(self.link) };
+        }
+    }
+
+    /// Follows the window when it's moved to another display.
+    pub fn set_display(&self, display_id: CGDirectDisplayID) {
+        unsafe { ffi::CVDisplayLinkSetCurrentCGDisplay(self.link, display_id) };
+    }
+}
+
+impl Drop for DisplayLink {
+    fn drop(&mut self) {
+        unsafe {
+            // Once stopped, the callback won't be invoked again, so the
+            // context can be released. The state mustn't be locked here, as
+            // this waits for a callback in progress to return.
+            ffi::CVDisplayLinkStop(self.link);
+            ffi::CVDisplayLinkRelease(self.link);
+            drop(Box::from_raw(self.context));
+        }
+    }
+}
+
+// Invoked on the display link's own thread, once per refresh.
+extern "C" fn output_callback(
+    _: ffi::CVDisplayLinkRef,
+    _: *const c_void,
+    _: *const c_void,
+    _: ffi::CVOptionFlags,
+    _: *mut ffi::CVOptionFlags,
+    context: *mut c_void,
+) -> ffi::CVReturn {
+    let context = unsafe { &*(context as *const Context) };
+    let mut state = context.state.lock().unwrap();
+    if state.redraw_requested {
+        state.redraw_requested = false;
+        AppState::queue_redraw(context.window_id);
+    } else {
+        // Stopping the display link from its own callback doesn't wait for
+        // the callback to return. Since the state is locked, a redraw
+        // requested in the meantime starts the display link again.
+        state.running = false;
+        unsafe { ffi::CVDisplayLinkStop(context.link) };
+    }
+    ffi::kCVReturnSuccess
+}
@@ -23,15 +23,18 @@ use raw_window_handle::{AppKitDisplayHandle, RawDisplayHandle};
 
 use crate::{
     event::Event,
-    event_loop::{ControlFlow, EventLoopClosed, EventLoopWindowTarget as RootWindowTarget},
+    event_loop::{
+        ControlFlow, DeviceEventFilter, EventLoopClosed, EventLoopWindowTarget as RootWindowTarget,
+    },
     monitor::MonitorHandle as RootMonitorHandle,
     platform::macos::ActivationPolicy,
     platform_impl::platform::{
-        app::WinitApplication,
+        app::{self, WinitApplication},
         app_delegate::ApplicationDelegate,
         app_state::{AppState, Callback},
         monitor::{self, MonitorHandle},
         observer::*,
+        util,
     },
 };
 
@@ -105,6 +108,33 @@ impl<T> EventLoopWindowTarget<T> {
         let app: cocoa::base::id = unsafe { msg_send![cls, sharedApplication] };
         unsafe { msg_send![app, hideOtherApplications: 0] }
     }
+
+    pub(crate) fn allows_automatic_window_tabbing(&self) -> bool {
+        let is_allowed: bool = unsafe { msg_send![class!(NSWindow), allowsAutomaticWindowTabbing] };
+        is_allowed
+    }
+
+    pub(crate) fn set_allows_automatic_window_tabbing(&self, enabled: bool) {
+        unsafe { msg_send![class!(NSWindow), setAllowsAutomaticWindowTabbing: enabled] }
+    }
+
+    pub fn set_device_event_filter(&self, filter: DeviceEventFilter) {
+        app::set_device_event_filter(filter);
+    }
+
+    pub(crate) fn monitor_list(&self) -> &'static monitor::MonitorList {
+        monitor::MonitorList::get()
+    }
+
+    pub(crate) fn reply_to_quit_request(&self, quit: bool) {
+        unsafe {
+            let _: () = msg_send![NSApp(), replyToApplicationShouldTerminate: quit];
+        }
+    }
+
+    pub(crate) fn system_accent_color(&self) -> (f64, f64, f64, f64) {
+        unsafe { util::system_accent_color() }
+    }
 }
 
 pub struct EventLoop<T: 'static> {
@@ -128,6 +158,9 @@ pub struct EventLoop<T: 'static> {
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) activation_policy: ActivationPolicy,
     pub(crate) default_menu: bool,
+    pub(crate) dpi_aware: bool,
+    pub(crate) activate_on_launch: bool,
+    pub(crate) deferred_termination: bool,
 }
 
 impl Default for PlatformSpecificEventLoopAttributes {
@@ -135,6 +168,9 @@ impl Default for PlatformSpecificEventLoopAttributes {
         Self {
             activation_policy: Default::default(), // Regular
             default_menu: true,
+            dpi_aware: true,
+            activate_on_launch: true,
+            deferred_termination: false,
         }
     }
 }
@@ -158,7 +194,13 @@ impl<T> EventLoop<T> {
                 ActivationPolicy::Accessory => NSApplicationActivationPolicyAccessory,
                 ActivationPolicy::Prohibited => NSApplicationActivationPolicyProhibited,
             };
-            let delegate = ApplicationDelegate::new(activation_policy, attributes.default_menu);
+            let delegate = ApplicationDelegate::new(
+                activation_policy,
+                attributes.default_menu,
+                attributes.activate_on_launch,
+                attributes.deferred_termination,
+            );
+            util::set_dpi_aware(attributes.dpi_aware);
 
             autoreleasepool(|_| {
                 let _: () = msg_send![app, setDelegate: &*delegate];
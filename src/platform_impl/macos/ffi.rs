@@ -6,7 +6,8 @@ use std::ffi::c_void;
 
 use cocoa::base::id;
 use core_foundation::{
-    array::CFArrayRef, dictionary::CFDictionaryRef, string::CFStringRef, uuid::CFUUIDRef,
+    array::CFArrayRef, data::CFDataRef, dictionary::CFDictionaryRef, string::CFStringRef,
+    uuid::CFUUIDRef,
 };
 use core_graphics::{
     base::CGError,
@@ -56,6 +57,64 @@ impl NSMutableAttributedString for id {
     }
 }
 
+// `NSEventSubtype` values for mouse events. The `NSEventSubtype` enum from
+// `cocoa` only covers the subtypes of `NSAppKitDefined` events.
+pub const NSEventSubtypeMouseEvent: i16 = 0;
+pub const NSEventSubtypeTabletPoint: i16 = 1;
+pub const NSEventSubtypeTabletProximity: i16 = 2;
+pub const NSEventSubtypeTouch: i16 = 3;
+
+pub type NSBitmapFormat = NSUInteger;
+
+pub const NSBitmapFormatAlphaNonpremultiplied: NSBitmapFormat = 1 << 1;
+
+pub type NSDragOperation = NSUInteger;
+
+pub const NSDragOperationNone: NSDragOperation = 0;
+pub const NSDragOperationCopy: NSDragOperation = 1;
+pub const NSDragOperationGeneric: NSDragOperation = 4;
+
+pub type NSLineBreakMode = NSUInteger;
+
+pub const NSLineBreakByClipping: NSLineBreakMode = 2;
+pub const NSLineBreakByTruncatingHead: NSLineBreakMode = 3;
+pub const NSLineBreakByTruncatingTail: NSLineBreakMode = 4;
+pub const NSLineBreakByTruncatingMiddle: NSLineBreakMode = 5;
+
+pub type NSVisualEffectMaterial = NSInteger;
+
+pub const NSVisualEffectMaterialAppearanceBased: NSVisualEffectMaterial = 0;
+pub const NSVisualEffectMaterialTitlebar: NSVisualEffectMaterial = 3;
+pub const NSVisualEffectMaterialSelection: NSVisualEffectMaterial = 4;
+pub const NSVisualEffectMaterialMenu: NSVisualEffectMaterial = 5;
+pub const NSVisualEffectMaterialPopover: NSVisualEffectMaterial = 6;
+pub const NSVisualEffectMaterialSidebar: NSVisualEffectMaterial = 7;
+pub const NSVisualEffectMaterialHeaderView: NSVisualEffectMaterial = 10;
+pub const NSVisualEffectMaterialSheet: NSVisualEffectMaterial = 11;
+pub const NSVisualEffectMaterialWindowBackground: NSVisualEffectMaterial = 12;
+pub const NSVisualEffectMaterialHUDWindow: NSVisualEffectMaterial = 13;
+pub const NSVisualEffectMaterialFullScreenUI: NSVisualEffectMaterial = 15;
+pub const NSVisualEffectMaterialToolTip: NSVisualEffectMaterial = 17;
+pub const NSVisualEffectMaterialContentBackground: NSVisualEffectMaterial = 18;
+pub const NSVisualEffectMaterialUnderWindowBackground: NSVisualEffectMaterial = 21;
+pub const NSVisualEffectMaterialUnderPageBackground: NSVisualEffectMaterial = 22;
+
+pub type NSVisualEffectBlendingMode = NSInteger;
+
+pub const NSVisualEffectBlendingModeBehindWindow: NSVisualEffectBlendingMode = 0;
+pub const NSVisualEffectBlendingModeWithinWindow: NSVisualEffectBlendingMode = 1;
+
+pub type NSWindowSharingType = NSUInteger;
+
+pub const NSWindowSharingNone: NSWindowSharingType = 0;
+pub const NSWindowSharingReadOnly: NSWindowSharingType = 1;
+
+pub type NSWindowTabbingMode = NSInteger;
+
+pub const NSWindowTabbingModeAutomatic: NSWindowTabbingMode = 0;
+pub const NSWindowTabbingModePreferred: NSWindowTabbingMode = 1;
+pub const NSWindowTabbingModeDisallowed: NSWindowTabbingMode = 2;
+
 pub const kCGBaseWindowLevelKey: NSInteger = 0;
 pub const kCGMinimumWindowLevelKey: NSInteger = 1;
 pub const kCGDesktopWindowLevelKey: NSInteger = 2;
@@ -135,7 +194,17 @@ pub const IOYUV422Pixels: &str = "Y4U2V2";
 pub const IO8BitOverlayPixels: &str = "O8";
 
 pub type CGWindowLevel = i32;
+pub type CGWindowLevelKey = i32;
 pub type CGDisplayModeRef = *mut c_void;
+pub type CGColorSpaceRef = *mut c_void;
+pub type CGDisplayChangeSummaryFlags = u32;
+pub type CGDisplayReconfigurationCallBack = extern "C" fn(
+    display: CGDirectDisplayID,
+    flags: CGDisplayChangeSummaryFlags,
+    userInfo: *mut c_void,
+);
+
+pub const kCGDisplayBeginConfigurationFlag: CGDisplayChangeSummaryFlags = 1 << 0;
 
 // `CGDisplayCreateUUIDFromDisplayID` comes from the `ColorSync` framework.
 // However, that framework was only introduced "publicly" in macOS 10.13.
@@ -178,6 +247,15 @@ extern "C" {
     ) -> CGError;
     pub fn CGReleaseDisplayFadeReservation(token: CGDisplayFadeReservationToken) -> CGError;
     pub fn CGShieldingWindowLevel() -> CGWindowLevel;
+    pub fn CGWindowLevelForKey(key: CGWindowLevelKey) -> CGWindowLevel;
+    pub fn CGDisplayRotation(display: CGDirectDisplayID) -> f64;
+    pub fn CGDisplayRegisterReconfigurationCallback(
+        callback: CGDisplayReconfigurationCallBack,
+        userInfo: *mut c_void,
+    ) -> CGError;
+    pub fn CGDisplayCopyColorSpace(display: CGDirectDisplayID) -> CGColorSpaceRef;
+    pub fn CGColorSpaceCopyICCProfile(space: CGColorSpaceRef) -> CFDataRef;
+    pub fn CGColorSpaceRelease(space: CGColorSpaceRef);
     pub fn CGDisplaySetDisplayMode(
         display: CGDirectDisplayID,
         mode: CGDisplayModeRef,
@@ -191,6 +269,7 @@ extern "C" {
     pub fn CGDisplayModeGetPixelHeight(mode: CGDisplayModeRef) -> usize;
     pub fn CGDisplayModeGetRefreshRate(mode: CGDisplayModeRef) -> f64;
     pub fn CGDisplayModeCopyPixelEncoding(mode: CGDisplayModeRef) -> CFStringRef;
+    pub fn CGDisplayCopyDisplayMode(display: CGDirectDisplayID) -> CGDisplayModeRef;
     pub fn CGDisplayModeRetain(mode: CGDisplayModeRef);
     pub fn CGDisplayModeRelease(mode: CGDisplayModeRef);
 }
@@ -222,12 +301,34 @@ mod core_video {
     // CVDisplayLink.h
 
     pub type CVDisplayLinkRef = *mut c_void;
+    pub type CVOptionFlags = u64; // uint64_t
+
+    // The `CVTimeStamp`s are only passed through, so they're left opaque.
+    pub type CVDisplayLinkOutputCallback = extern "C" fn(
+        displayLink: CVDisplayLinkRef,
+        inNow: *const c_void,
+        inOutputTime: *const c_void,
+        flagsIn: CVOptionFlags,
+        flagsOut: *mut CVOptionFlags,
+        displayLinkContext: *mut c_void,
+    ) -> CVReturn;
 
     extern "C" {
         pub fn CVDisplayLinkCreateWithCGDisplay(
             displayID: CGDirectDisplayID,
             displayLinkOut: *mut CVDisplayLinkRef,
         ) -> CVReturn;
+        pub fn CVDisplayLinkSetCurrentCGDisplay(
+            displayLink: CVDisplayLinkRef,
+            displayID: CGDirectDisplayID,
+        ) -> CVReturn;
+        pub fn CVDisplayLinkSetOutputCallback(
+            displayLink: CVDisplayLinkRef,
+            callback: CVDisplayLinkOutputCallback,
+            userInfo: *mut c_void,
+        ) -> CVReturn;
+        pub fn CVDisplayLinkStart(displayLink: CVDisplayLinkRef) -> CVReturn;
+        pub fn CVDisplayLinkStop(displayLink: CVDisplayLinkRef) -> CVReturn;
         pub fn CVDisplayLinkGetNominalOutputVideoRefreshPeriod(
             displayLink: CVDisplayLinkRef,
         ) -> CVTime;
@@ -8,6 +8,7 @@ mod app;
 mod app_delegate;
 mod app_state;
 mod appkit;
+mod display_link;
 mod event;
 mod event_loop;
 mod ffi;
@@ -21,10 +22,11 @@ mod window_delegate;
 use std::{fmt, ops::Deref, sync::Arc};
 
 pub(crate) use self::{
+    event::scancode_to_keycode,
     event_loop::{
         EventLoop, EventLoopProxy, EventLoopWindowTarget, PlatformSpecificEventLoopAttributes,
     },
-    monitor::{MonitorHandle, VideoMode},
+    monitor::{MonitorHandle, MonitorList, VideoMode},
     window::{PlatformSpecificWindowBuilderAttributes, UnownedWindow, WindowId},
 };
 use crate::{
@@ -32,7 +34,7 @@ use crate::{
 };
 use objc::rc::autoreleasepool;
 
-pub(crate) use crate::icon::NoIcon as PlatformIcon;
+pub(crate) use crate::icon::RgbaIcon as PlatformIcon;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DeviceId;
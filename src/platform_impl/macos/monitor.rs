@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, fmt};
+use std::{collections::VecDeque, fmt, os::raw::c_void, ptr, sync::Mutex};
 
 use super::{ffi, util};
 use crate::{
@@ -12,10 +12,14 @@ use cocoa::{
 use core_foundation::{
     array::{CFArrayGetCount, CFArrayGetValueAtIndex},
     base::{CFRelease, TCFType},
+    data::CFData,
     string::CFString,
+    uuid::{CFUUIDGetUUIDBytes, CFUUID},
 };
 use core_graphics::display::{CGDirectDisplayID, CGDisplay, CGDisplayBounds};
+use core_graphics::geometry::CGRect;
 use objc::foundation::NSUInteger;
+use once_cell::sync::Lazy;
 
 #[derive(Clone)]
 pub struct VideoMode {
@@ -98,18 +102,17 @@ impl VideoMode {
     }
 }
 
+/// The display id, and the bytes of the display's UUID, which are looked up
+/// once when the handle is created.
 #[derive(Clone)]
-pub struct MonitorHandle(CGDirectDisplayID);
+pub struct MonitorHandle(CGDirectDisplayID, Option<[u8; 16]>);
 
 // `CGDirectDisplayID` changes on video mode change, so we cannot rely on that
 // for comparisons, but we can use `CGDisplayCreateUUIDFromDisplayID` to get an
 // unique identifier that persists even across system reboots
 impl PartialEq for MonitorHandle {
     fn eq(&self, other: &Self) -> bool {
-        unsafe {
-            ffi::CGDisplayCreateUUIDFromDisplayID(self.0)
-                == ffi::CGDisplayCreateUUIDFromDisplayID(other.0)
-        }
+        self.stable_id() == other.stable_id()
     }
 }
 
@@ -123,18 +126,13 @@ impl PartialOrd for MonitorHandle {
 
 impl Ord for MonitorHandle {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        unsafe {
-            ffi::CGDisplayCreateUUIDFromDisplayID(self.0)
-                .cmp(&ffi::CGDisplayCreateUUIDFromDisplayID(other.0))
-        }
+        self.stable_id().cmp(&other.stable_id())
     }
 }
 
 impl std::hash::Hash for MonitorHandle {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        unsafe {
-            ffi::CGDisplayCreateUUIDFromDisplayID(self.0).hash(state);
-        }
+        self.stable_id().hash(state);
     }
 }
 
@@ -142,7 +140,7 @@ pub fn available_monitors() -> VecDeque<MonitorHandle> {
     if let Ok(displays) = CGDisplay::active_displays() {
         let mut monitors = VecDeque::with_capacity(displays.len());
         for display in displays {
-            monitors.push_back(MonitorHandle(display));
+            monitors.push_back(MonitorHandle::new(display));
         }
         monitors
     } else {
@@ -150,8 +148,170 @@ pub fn available_monitors() -> VecDeque<MonitorHandle> {
     }
 }
 
+/// The monitors of the system, kept up to date from the display reconfiguration
+/// callback of Core Graphics, see `EventLoopWindowTargetExtMacOS::monitor_list`.
+#[derive(Debug)]
+pub struct MonitorList {
+    state: Mutex<MonitorListState>,
+}
+
+#[derive(Debug)]
+struct MonitorListState {
+    monitors: Vec<MonitorHandle>,
+    generation: u64,
+}
+
+static MONITOR_LIST: Lazy<MonitorList> = Lazy::new(|| {
+    let list = MonitorList {
+        state: Mutex::new(MonitorListState {
+            monitors: available_monitors().into(),
+            generation: 0,
+        }),
+    };
+    // The callback is never unregistered, as the list lives until the process
+    // exits.
+    unsafe { ffi::CGDisplayRegisterReconfigurationCallback(display_reconfigured, ptr::null_mut()) };
+    list
+});
+
+impl MonitorList {
+    pub fn get() -> &'static MonitorList {
+        &MONITOR_LIST
+    }
+
+    pub fn monitors(&self) -> Vec<MonitorHandle> {
+        self.state.lock().unwrap().monitors.clone()
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.state.lock().unwrap().generation
+    }
+
+    // The monitors that are still connected keep their order, and new ones are
+    // appended. The handles are replaced with current ones, as the display ID
+    // of a monitor may change along with its mode, see `PartialEq`.
+    fn refresh(&self) {
+        let current = available_monitors();
+        let mut state = self.state.lock().unwrap();
+        let mut monitors: Vec<MonitorHandle> = state
+            .monitors
+            .iter()
+            .filter_map(|monitor| current.iter().find(|current| *current == monitor).cloned())
+            .collect();
+        for monitor in current {
+            if !monitors.contains(&monitor) {
+                monitors.push(monitor);
+            }
+        }
+        state.monitors = monitors;
+        state.generation += 1;
+    }
+}
+
+// Invoked on the main thread, once before and once after each change, for
+// each display that's affected.
+extern "C" fn display_reconfigured(
+    _: CGDirectDisplayID,
+    flags: ffi::CGDisplayChangeSummaryFlags,
+    _: *mut c_void,
+) {
+    if flags & ffi::kCGDisplayBeginConfigurationFlag == 0 {
+        MONITOR_LIST.refresh();
+    }
+}
+
+/// Returns the monitor with the menu bar, which is what users set as the main
+/// display in the arrangement of the Displays settings.
+///
+/// That's the first of `NSScreen.screens`. `CGMainDisplayID` is the display at
+/// the origin of the global display space instead, which is only documented to
+/// *typically* be the one with the menu bar, so it's only a fallback.
 pub fn primary_monitor() -> MonitorHandle {
-    MonitorHandle(CGDisplay::main().id)
+    MonitorHandle::new(primary_display_id())
+}
+
+/// Returns the display id of the monitor with the menu bar, see
+/// `primary_monitor`.
+pub(crate) fn primary_display_id() -> CGDirectDisplayID {
+    select_primary_display(&screen_display_ids(), CGDisplay::main().id)
+}
+
+// The display ids of `NSScreen.screens`, in order, or `None` for a screen
+// without one.
+fn screen_display_ids() -> Vec<Option<CGDirectDisplayID>> {
+    unsafe {
+        let screens = NSScreen::screens(nil);
+        let count: NSUInteger = msg_send![screens, count];
+        let key = util::ns_string_id_ref("NSScreenNumber");
+        (0..count)
+            .map(|i| {
+                let screen: id = msg_send![screens, objectAtIndex: i];
+                let value: id = msg_send![NSScreen::deviceDescription(screen), objectForKey: *key];
+                if value != nil {
+                    let display_id: NSUInteger = msg_send![value, unsignedIntegerValue];
+                    Some(display_id as CGDirectDisplayID)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+fn select_primary_display(
+    screen_display_ids: &[Option<CGDirectDisplayID>],
+    main_display_id: CGDirectDisplayID,
+) -> CGDirectDisplayID {
+    screen_display_ids
+        .first()
+        .copied()
+        .flatten()
+        .unwrap_or(main_display_id)
+}
+
+/// Returns the monitor closest to `point`, which is in points in the global display space
+/// (i.e. with the origin at the top-left of the main display).
+pub fn nearest_monitor(point: (f64, f64)) -> MonitorHandle {
+    let distance = |monitor: &MonitorHandle| {
+        let bounds = unsafe { CGDisplayBounds(monitor.native_identifier()) };
+        squared_distance(point, bounds)
+    };
+    available_monitors()
+        .into_iter()
+        .min_by(|a, b| {
+            distance(a)
+                .partial_cmp(&distance(b))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap_or_else(primary_monitor)
+}
+
+/// The squared distance from `point` to the closest point of `bounds`, which is
+/// zero if the point is inside.
+fn squared_distance(point: (f64, f64), bounds: CGRect) -> f64 {
+    let (min_x, min_y) = (bounds.origin.x as f64, bounds.origin.y as f64);
+    let (max_x, max_y) = (
+        min_x + bounds.size.width as f64,
+        min_y + bounds.size.height as f64,
+    );
+    let dx = (min_x - point.0).max(point.0 - max_x).max(0.0);
+    let dy = (min_y - point.1).max(point.1 - max_y).max(0.0);
+    dx * dx + dy * dy
+}
+
+/// `CGDisplayCreateUUIDFromDisplayID` returns a new object on every call, so we
+/// must compare the bytes of the UUID rather than the pointers.
+fn display_uuid(display_id: CGDirectDisplayID) -> Option<[u8; 16]> {
+    let uuid = unsafe { ffi::CGDisplayCreateUUIDFromDisplayID(display_id) };
+    if uuid.is_null() {
+        return None;
+    }
+    let uuid = unsafe { CFUUID::wrap_under_create_rule(uuid) };
+    let b = unsafe { CFUUIDGetUUIDBytes(uuid.as_concrete_TypeRef()) };
+    Some([
+        b.byte0, b.byte1, b.byte2, b.byte3, b.byte4, b.byte5, b.byte6, b.byte7, b.byte8, b.byte9,
+        b.byte10, b.byte11, b.byte12, b.byte13, b.byte14, b.byte15,
+    ])
 }
 
 impl fmt::Debug for MonitorHandle {
@@ -181,11 +341,24 @@ impl fmt::Debug for MonitorHandle {
 
 impl MonitorHandle {
     pub fn new(id: CGDirectDisplayID) -> Self {
-        MonitorHandle(id)
+        MonitorHandle(id, display_uuid(id))
+    }
+
+    /// The identifier used for comparing and hashing monitors.
+    ///
+    /// The UUID is looked up when the handle is created, so that the
+    /// identifier of a handle doesn't change when its display is disconnected.
+    /// Displays that weren't connected at that point don't have a UUID, and
+    /// fall back to the display id.
+    fn stable_id(&self) -> (Option<[u8; 16]>, CGDirectDisplayID) {
+        match self.1 {
+            Some(uuid) => (Some(uuid), 0),
+            None => (None, self.0),
+        }
     }
 
     pub fn name(&self) -> Option<String> {
-        let MonitorHandle(display_id) = *self;
+        let display_id = self.0;
         let screen_num = CGDisplay::new(display_id).model_number();
         Some(format!("Monitor #{}", screen_num))
     }
@@ -196,11 +369,14 @@ impl MonitorHandle {
     }
 
     pub fn size(&self) -> PhysicalSize<u32> {
-        let MonitorHandle(display_id) = *self;
-        let display = CGDisplay::new(display_id);
-        let height = display.pixels_high();
-        let width = display.pixels_wide();
-        PhysicalSize::from_logical::<_, f64>((width as f64, height as f64), self.scale_factor())
+        // Unlike the display mode, which `CGDisplayPixelsWide` and
+        // `CGDisplayPixelsHigh` follow, the bounds are rotated along with the
+        // display, as is the global coordinate space that windows are placed in.
+        let bounds = unsafe { CGDisplayBounds(self.native_identifier()) };
+        PhysicalSize::from_logical::<_, f64>(
+            (bounds.size.width as f64, bounds.size.height as f64),
+            self.scale_factor(),
+        )
     }
 
     #[inline]
@@ -212,12 +388,40 @@ impl MonitorHandle {
         )
     }
 
+    /// Whether this is the built-in display of a laptop.
+    pub fn is_builtin(&self) -> bool {
+        CGDisplay::new(self.native_identifier()).is_builtin()
+    }
+
+    /// The rotation of the display in degrees, as set in System Settings.
+    pub fn rotation(&self) -> f64 {
+        unsafe { ffi::CGDisplayRotation(self.native_identifier()) }
+    }
+
+    /// The ICC profile of the display's color space.
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        unsafe {
+            let color_space = ffi::CGDisplayCopyColorSpace(self.native_identifier());
+            if color_space.is_null() {
+                return None;
+            }
+            // `CGColorSpaceCopyICCData` replaces this as of macOS 10.12, which
+            // is too recent to link against.
+            let data = ffi::CGColorSpaceCopyICCProfile(color_space);
+            ffi::CGColorSpaceRelease(color_space);
+            if data.is_null() {
+                return None;
+            }
+            Some(CFData::wrap_under_create_rule(data).bytes().to_vec())
+        }
+    }
+
     pub fn scale_factor(&self) -> f64 {
         let screen = match self.ns_screen() {
             Some(screen) => screen,
             None => return 1.0, // default to 1.0 when we can't find the screen
         };
-        unsafe { NSScreen::backingScaleFactor(screen) as f64 }
+        util::scale_factor(unsafe { NSScreen::backingScaleFactor(screen) })
     }
 
     pub fn refresh_rate_millihertz(&self) -> Option<u32> {
@@ -321,3 +525,70 @@ impl MonitorHandle {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    use core_graphics::geometry::{CGPoint, CGSize};
+
+    use super::*;
+
+    fn hash(monitor: &MonitorHandle) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        monitor.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // `Window::current_monitor` creates its handle from the window's display
+    // id, like `main` below.
+    #[test]
+    fn handles_of_the_same_display_compare_equal() {
+        let main = MonitorHandle::new(CGDisplay::main().id);
+        let available = available_monitors();
+        let found = available
+            .iter()
+            .find(|monitor| monitor.native_identifier() == main.native_identifier())
+            .expect("the main display isn't one of the available monitors");
+        assert_eq!(*found, main);
+        assert_eq!(hash(found), hash(&main));
+    }
+
+    // The display with the menu bar is at the origin of both AppKit's and Core
+    // Graphics' screen coordinates.
+    #[test]
+    fn primary_display_is_the_one_with_the_menu_bar() {
+        // With two displays, where the menu bar was moved to display 2, which
+        // isn't `CGMainDisplayID`.
+        assert_eq!(select_primary_display(&[Some(2), Some(1)], 1), 2);
+        assert_eq!(select_primary_display(&[Some(1), Some(2)], 1), 1);
+        // Without screens, or a screen number, the main display is used.
+        assert_eq!(select_primary_display(&[], 1), 1);
+        assert_eq!(select_primary_display(&[None, Some(2)], 1), 1);
+    }
+
+    #[test]
+    fn primary_monitor_is_at_the_origin() {
+        let primary = primary_monitor();
+        assert!(available_monitors().contains(&primary));
+        let bounds = unsafe { CGDisplayBounds(primary.native_identifier()) };
+        assert_eq!((bounds.origin.x, bounds.origin.y), (0.0, 0.0));
+    }
+
+    #[test]
+    fn squared_distance_to_the_closest_point() {
+        let bounds = CGRect::new(&CGPoint::new(0.0, 0.0), &CGSize::new(1920.0, 1080.0));
+        assert_eq!(squared_distance((100.0, 100.0), bounds), 0.0);
+        assert_eq!(squared_distance((1920.0, 1080.0), bounds), 0.0);
+        assert_eq!(squared_distance((-30.0, 500.0), bounds), 900.0);
+        assert_eq!(squared_distance((500.0, 1090.0), bounds), 100.0);
+        assert_eq!(squared_distance((1923.0, -4.0), bounds), 25.0);
+    }
+
+    #[test]
+    fn nearest_monitor_of_a_point_on_the_main_display() {
+        let main = MonitorHandle::new(CGDisplay::main().id);
+        assert_eq!(nearest_monitor((1.0, 1.0)), main);
+    }
+}
@@ -57,10 +57,43 @@ extern "C" {
     pub fn CFRunLoopSourceInvalidate(source: CFRunLoopSourceRef);
     pub fn CFRunLoopSourceSignal(source: CFRunLoopSourceRef);
 
-    pub fn CFAbsoluteTimeGetCurrent() -> CFAbsoluteTime;
     pub fn CFRelease(cftype: *const c_void);
 }
 
+// libdispatch is part of libSystem, which is always linked.
+extern "C" {
+    static _dispatch_main_q: c_void;
+    static _dispatch_source_type_timer: c_void;
+
+    fn dispatch_source_create(
+        type_: *const c_void,
+        handle: usize,
+        mask: usize,
+        queue: *const c_void,
+    ) -> DispatchSourceRef;
+    fn dispatch_source_set_timer(
+        source: DispatchSourceRef,
+        start: DispatchTime,
+        interval: u64,
+        leeway: u64,
+    );
+    fn dispatch_source_set_event_handler_f(
+        source: DispatchSourceRef,
+        handler: extern "C" fn(context: *mut c_void),
+    );
+    fn dispatch_source_cancel(source: DispatchSourceRef);
+    fn dispatch_resume(object: DispatchSourceRef);
+    fn dispatch_release(object: DispatchSourceRef);
+    fn dispatch_time(when: DispatchTime, delta: i64) -> DispatchTime;
+}
+
+type DispatchSourceRef = *mut c_void;
+type DispatchTime = u64;
+
+const DISPATCH_TIME_NOW: DispatchTime = 0;
+const DISPATCH_TIME_FOREVER: DispatchTime = !0;
+const DISPATCH_TIMER_STRICT: usize = 0x1;
+
 pub enum CFAllocator {}
 pub type CFAllocatorRef = *mut CFAllocator;
 pub enum CFRunLoop {}
@@ -244,6 +277,11 @@ pub fn setup_control_flow_observers(panic_info: Weak<PanicInfo>) {
 
 pub struct EventLoopWaker {
     timer: CFRunLoopTimerRef,
+    // Wakes the main loop for `ControlFlow::WaitUntil`. Run loop timers may be
+    // coalesced by the system and fire late by several milliseconds, whereas a
+    // strict dispatch timer fires as close to its deadline as possible. Its
+    // handler is run on the main queue, which is serviced by the main run loop.
+    deadline: DispatchSourceRef,
 }
 
 impl Drop for EventLoopWaker {
@@ -251,6 +289,8 @@ impl Drop for EventLoopWaker {
         unsafe {
             CFRunLoopTimerInvalidate(self.timer);
             CFRelease(self.timer as _);
+            dispatch_source_cancel(self.deadline);
+            dispatch_release(self.deadline);
         }
     }
 }
@@ -272,18 +312,36 @@ impl Default for EventLoopWaker {
                 ptr::null_mut(),
             );
             CFRunLoopAddTimer(CFRunLoopGetMain(), timer, kCFRunLoopCommonModes);
-            EventLoopWaker { timer }
+
+            extern "C" fn wakeup_main_queue(_context: *mut c_void) {}
+            let deadline = dispatch_source_create(
+                &_dispatch_source_type_timer,
+                0,
+                DISPATCH_TIMER_STRICT,
+                &_dispatch_main_q,
+            );
+            dispatch_source_set_event_handler_f(deadline, wakeup_main_queue);
+            dispatch_source_set_timer(deadline, DISPATCH_TIME_FOREVER, DISPATCH_TIME_FOREVER, 0);
+            dispatch_resume(deadline);
+
+            EventLoopWaker { timer, deadline }
         }
     }
 }
 
 impl EventLoopWaker {
     pub fn stop(&mut self) {
-        unsafe { CFRunLoopTimerSetNextFireDate(self.timer, std::f64::MAX) }
+        unsafe {
+            CFRunLoopTimerSetNextFireDate(self.timer, std::f64::MAX);
+            self.set_deadline(DISPATCH_TIME_FOREVER);
+        }
     }
 
     pub fn start(&mut self) {
-        unsafe { CFRunLoopTimerSetNextFireDate(self.timer, std::f64::MIN) }
+        unsafe {
+            self.set_deadline(DISPATCH_TIME_FOREVER);
+            CFRunLoopTimerSetNextFireDate(self.timer, std::f64::MIN);
+        }
     }
 
     pub fn start_at(&mut self, instant: Instant) {
@@ -292,12 +350,15 @@ impl EventLoopWaker {
             self.start();
         } else {
             unsafe {
-                let current = CFAbsoluteTimeGetCurrent();
-                let duration = instant - now;
-                let fsecs =
-                    duration.subsec_nanos() as f64 / 1_000_000_000.0 + duration.as_secs() as f64;
-                CFRunLoopTimerSetNextFireDate(self.timer, current + fsecs)
+                CFRunLoopTimerSetNextFireDate(self.timer, std::f64::MAX);
+                let nanos = (instant - now).as_nanos().min(i64::MAX as u128) as i64;
+                self.set_deadline(dispatch_time(DISPATCH_TIME_NOW, nanos));
             }
         }
     }
+
+    // A one-shot timer firing at `start`, with no leeway.
+    unsafe fn set_deadline(&mut self, start: DispatchTime) {
+        dispatch_source_set_timer(self.deadline, start, DISPATCH_TIME_FOREVER, 0);
+    }
 }
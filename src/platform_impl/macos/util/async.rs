@@ -1,25 +1,39 @@
 use std::{
     ops::Deref,
+    ptr,
     sync::{Mutex, Weak},
 };
 
 use cocoa::{
-    appkit::{CGFloat, NSScreen, NSWindow, NSWindowStyleMask},
+    appkit::{
+        CGFloat, NSApp, NSScreen, NSView, NSViewHeightSizable, NSViewWidthSizable, NSWindow,
+        NSWindowButton, NSWindowOrderingMode, NSWindowStyleMask,
+    },
     base::{id, nil},
-    foundation::{NSPoint, NSSize, NSString},
+    foundation::{NSPoint, NSRect, NSSize, NSString},
 };
+use core_graphics::display::CGDirectDisplayID;
 use dispatch::Queue;
-use objc::foundation::is_main_thread;
+use objc::foundation::{is_main_thread, NSInteger, NSUInteger};
 use objc::rc::autoreleasepool;
 use objc::runtime::Bool;
 
 use crate::{
     dpi::LogicalSize,
+    event::{Event, WindowEvent},
     platform_impl::platform::{
+        app_state::AppState,
+        event::EventWrapper,
         ffi,
+        monitor::{MonitorHandle, NativeDisplayMode},
         util::IdRef,
-        window::{SharedState, SharedStateMutexGuard},
+        view,
+        window::{
+            apply_inner_size_constraints, clamp_content_size, get_window_id, set_max_inner_size,
+            set_min_inner_size, SharedState, SharedStateMutexGuard,
+        },
     },
+    window::{Theme, WindowId},
 };
 
 // Unsafe wrapper type that allows us to dispatch things that aren't Send.
@@ -30,6 +44,12 @@ struct MainThreadSafe<T>(T);
 
 unsafe impl<T> Send for MainThreadSafe<T> {}
 
+impl<T> MainThreadSafe<T> {
+    fn into_inner(self) -> T {
+        self.0
+    }
+}
+
 impl<T> Deref for MainThreadSafe<T> {
     type Target = T;
     fn deref(&self) -> &T {
@@ -38,7 +58,12 @@ impl<T> Deref for MainThreadSafe<T> {
 }
 
 unsafe fn set_style_mask(ns_window: id, ns_view: id, mask: NSWindowStyleMask) {
+    // Changing the style mask can reset `ignoresMouseEvents`, which would make
+    // a click-through window (see `set_cursor_hittest`) catch the cursor again.
+    let ignores_mouse_events: Bool = msg_send![ns_window, ignoresMouseEvents];
     ns_window.setStyleMask_(mask);
+    ns_window.setIgnoresMouseEvents_(ignores_mouse_events.as_raw());
+    super::update_zoom_button(ns_window);
     // If we don't do this, key handling will break
     // (at least until the window is clicked again/etc.)
     ns_window.makeFirstResponder_(ns_view);
@@ -67,12 +92,127 @@ pub unsafe fn set_style_mask_sync(ns_window: id, ns_view: id, mask: NSWindowStyl
     }
 }
 
+pub unsafe fn set_min_inner_size_sync(ns_window: id, size: LogicalSize<f64>) {
+    if is_main_thread() {
+        set_min_inner_size(ns_window, size);
+    } else {
+        let ns_window = MainThreadSafe(ns_window);
+        Queue::main().exec_sync(move || {
+            set_min_inner_size(*ns_window, size);
+        })
+    }
+}
+
+pub unsafe fn set_max_inner_size_sync(ns_window: id, size: LogicalSize<f64>) {
+    if is_main_thread() {
+        set_max_inner_size(ns_window, size);
+    } else {
+        let ns_window = MainThreadSafe(ns_window);
+        Queue::main().exec_sync(move || {
+            set_max_inner_size(*ns_window, size);
+        })
+    }
+}
+
+// Resizing the window isn't thread-safe, and its `Resized` event can only be
+// queued from the main thread.
+pub unsafe fn apply_inner_size_constraints_async(ns_window: id) {
+    let ns_window = MainThreadSafe(ns_window);
+    Queue::main().exec_async(move || {
+        apply_inner_size_constraints(*ns_window);
+    });
+}
+
+// Events can only be queued from the main thread.
+pub unsafe fn queue_window_event_async(ns_window: id, event: WindowEvent<'static>) {
+    let ns_window = MainThreadSafe(ns_window);
+    Queue::main().exec_async(move || {
+        AppState::queue_event(EventWrapper::StaticEvent(Event::WindowEvent {
+            window_id: WindowId(get_window_id(*ns_window)),
+            event,
+        }));
+    });
+}
+
+// `[NSCursor hide]` and `[NSCursor unhide]` aren't thread-safe.
+pub unsafe fn update_cursor_hidden_async(ns_view: id) {
+    let ns_view = MainThreadSafe(ns_view);
+    Queue::main().exec_async(move || {
+        view::update_cursor_hidden(*ns_view);
+    });
+}
+
+// Views may only be accessed from the main thread.
+pub unsafe fn set_titlebar_buttons_hidden_async(ns_window: id, hidden: bool) {
+    let ns_window = MainThreadSafe(ns_window);
+    Queue::main().exec_async(move || {
+        super::set_titlebar_buttons_hidden(*ns_window, hidden);
+    });
+}
+
+// `NSScreen` may only be used from the main thread, and the window may have
+// been resized in the meantime.
+pub unsafe fn center_on_monitor_async(ns_window: id, monitor: MonitorHandle) {
+    let ns_window = MainThreadSafe(ns_window);
+    Queue::main().exec_async(move || {
+        let ns_window = ns_window.into_inner();
+        match monitor.ns_screen() {
+            Some(ns_screen) => super::center_on_screen(ns_window, ns_screen),
+            None => warn!("`Window::center_on_monitor` called with a disconnected monitor"),
+        }
+    });
+}
+
+// Sheets may only be presented and ended from the main thread.
+pub unsafe fn begin_sheet_async(ns_window: id, parent: id) {
+    let ns_window = MainThreadSafe(ns_window);
+    let parent = MainThreadSafe(parent);
+    Queue::main().exec_async(move || {
+        // The sheet is shown by its parent, it must not be visible before.
+        ns_window.orderOut_(nil);
+        let _: () = msg_send![*parent, beginSheet: *ns_window, completionHandler: nil];
+    });
+}
+
+// Sheets may only be presented and ended from the main thread.
+pub unsafe fn end_sheet_async(ns_window: id) {
+    let ns_window = MainThreadSafe(ns_window);
+    Queue::main().exec_async(move || {
+        super::end_sheet(*ns_window);
+    });
+}
+
+// Layers of views may only be accessed from the main thread.
+pub unsafe fn set_corner_radius_async(ns_window: id, ns_view: id, radius: Option<f64>) {
+    let ns_window = MainThreadSafe(ns_window);
+    let ns_view = MainThreadSafe(ns_view);
+    Queue::main().exec_async(move || {
+        super::set_corner_radius(*ns_window, *ns_view, radius);
+    });
+}
+
+// Layers of views may only be accessed from the main thread.
+pub unsafe fn set_contents_scale_async(ns_view: id, scale: f64) {
+    let ns_view = MainThreadSafe(ns_view);
+    Queue::main().exec_async(move || {
+        super::set_contents_scale(*ns_view, scale);
+    });
+}
+
 // `setContentSize:` isn't thread-safe either, though it doesn't log any errors
-// and just fails silently. Anyway, GCD to the rescue!
+// and just fails silently. Anyway, GCD to the rescue! The cached geometry is
+// refreshed once the window is resized, from `windowDidResize:`.
 pub unsafe fn set_content_size_async(ns_window: id, size: LogicalSize<f64>) {
     let ns_window = MainThreadSafe(ns_window);
     Queue::main().exec_async(move || {
-        ns_window.setContentSize_(NSSize::new(size.width as CGFloat, size.height as CGFloat));
+        // `setContentSize:` ignores `contentMinSize` and `contentMaxSize`, so
+        // clamp to them here.
+        let size = NSSize::new(size.width as CGFloat, size.height as CGFloat);
+        ns_window.setContentSize_(clamp_content_size(
+            size,
+            NSWindow::contentMinSize(*ns_window),
+            NSWindow::contentMaxSize(*ns_window),
+        ));
     });
 }
 
@@ -85,11 +225,42 @@ pub unsafe fn set_frame_top_left_point_async(ns_window: id, point: NSPoint) {
     });
 }
 
+// `setFrame:display:` isn't thread-safe.
+pub unsafe fn set_frame_async(ns_window: id, frame: NSRect) {
+    let ns_window = MainThreadSafe(ns_window);
+    Queue::main().exec_async(move || {
+        ns_window.setFrame_display_(frame, Bool::YES.as_raw());
+    });
+}
+
+// `setFrame:display:` isn't thread-safe, and neither is `NSAnimationContext`,
+// which is a per-thread object.
+pub unsafe fn set_frame_animated_async(ns_window: id, frame: NSRect, duration: f64) {
+    let ns_window = MainThreadSafe(ns_window);
+    Queue::main().exec_async(move || {
+        let _: () = msg_send![class!(NSAnimationContext), beginGrouping];
+        let context: id = msg_send![class!(NSAnimationContext), currentContext];
+        let _: () = msg_send![context, setDuration: duration.max(0.0)];
+        // Going through the animator proxy retargets any animation that is
+        // still in flight, starting from the window's current frame.
+        let animator: id = msg_send![*ns_window, animator];
+        let _: () = msg_send![animator, setFrame: frame, display: true];
+        let _: () = msg_send![class!(NSAnimationContext), endGrouping];
+    });
+}
+
 // `setFrameTopLeftPoint:` isn't thread-safe, and fails silently.
 pub unsafe fn set_level_async(ns_window: id, level: ffi::NSWindowLevel) {
     let ns_window = MainThreadSafe(ns_window);
     Queue::main().exec_async(move || {
-        ns_window.setLevel_(level as _);
+        super::set_level(*ns_window, level as _);
+    });
+}
+
+pub unsafe fn set_raw_level_async(ns_window: id, level: NSInteger) {
+    let ns_window = MainThreadSafe(ns_window);
+    Queue::main().exec_async(move || {
+        super::set_level(*ns_window, level);
     });
 }
 
@@ -101,6 +272,30 @@ pub unsafe fn set_ignore_mouse_events(ns_window: id, ignore: bool) {
     });
 }
 
+// `setWantsBestResolutionOpenGLSurface:` isn't thread-safe. The view keeps its
+// logical size and the window keeps its backing scale factor, but the pixel
+// size of the drawable changes, so we queue a `Resized` event from the main
+// thread to let the application reconfigure its surface.
+pub unsafe fn set_wants_best_resolution_opengl_surface_async(
+    ns_window: id,
+    ns_view: id,
+    wants_best_resolution: bool,
+) {
+    let ns_window = MainThreadSafe(ns_window);
+    let ns_view = MainThreadSafe(ns_view);
+    Queue::main().exec_async(move || {
+        ns_view.setWantsBestResolutionOpenGLSurface_(Bool::new(wants_best_resolution).as_raw());
+        let frame = NSView::frame(*ns_view);
+        let scale_factor = super::scale_factor(NSWindow::backingScaleFactor(*ns_window));
+        let size = LogicalSize::new(frame.size.width as f64, frame.size.height as f64)
+            .to_physical(scale_factor);
+        AppState::queue_event(EventWrapper::StaticEvent(Event::WindowEvent {
+            window_id: WindowId(get_window_id(*ns_window)),
+            event: WindowEvent::Resized(size),
+        }));
+    });
+}
+
 // `toggleFullScreen` is thread-safe, but our additional logic to account for
 // window styles isn't.
 pub unsafe fn toggle_full_screen_async(
@@ -139,10 +334,29 @@ pub unsafe fn toggle_full_screen_async(
     });
 }
 
-pub unsafe fn restore_display_mode_async(ns_screen: u32) {
+pub unsafe fn restore_display_mode_async(
+    ns_screen: u32,
+    saved_mode: Option<(CGDirectDisplayID, NativeDisplayMode)>,
+) {
     Queue::main().exec_async(move || {
-        ffi::CGRestorePermanentDisplayConfiguration();
-        assert_eq!(ffi::CGDisplayRelease(ns_screen), ffi::kCGErrorSuccess);
+        let restored = match saved_mode {
+            Some((display_id, ref mode)) if display_id == ns_screen => {
+                ffi::CGDisplaySetDisplayMode(ns_screen, mode.0, ptr::null()) == ffi::kCGErrorSuccess
+            }
+            _ => false,
+        };
+        if !restored {
+            ffi::CGRestorePermanentDisplayConfiguration();
+        }
+        // The display may have been disconnected in the meantime, which
+        // isn't worth panicking over.
+        let status = ffi::CGDisplayRelease(ns_screen);
+        if status != ffi::kCGErrorSuccess {
+            warn!(
+                "Failed to release display {}: CGError {}",
+                ns_screen, status
+            );
+        }
     });
 }
 
@@ -156,40 +370,59 @@ pub unsafe fn set_maximized_async(
     let ns_window = MainThreadSafe(ns_window);
     let shared_state = MainThreadSafe(shared_state);
     Queue::main().exec_async(move || {
-        if let Some(shared_state) = shared_state.upgrade() {
-            let mut shared_state_lock =
-                SharedStateMutexGuard::new(shared_state.lock().unwrap(), "set_maximized");
+        set_maximized(*ns_window, is_zoomed, maximized, &shared_state);
+    });
+}
 
-            // Save the standard frame sized if it is not zoomed
-            if !is_zoomed {
-                shared_state_lock.standard_frame = Some(NSWindow::frame(*ns_window));
-            }
+pub unsafe fn set_maximized_sync(
+    ns_window: id,
+    is_zoomed: bool,
+    maximized: bool,
+    shared_state: Weak<Mutex<SharedState>>,
+) {
+    if is_main_thread() {
+        set_maximized(ns_window, is_zoomed, maximized, &shared_state);
+    } else {
+        let ns_window = MainThreadSafe(ns_window);
+        let shared_state = MainThreadSafe(shared_state);
+        Queue::main().exec_sync(move || {
+            set_maximized(*ns_window, is_zoomed, maximized, &shared_state);
+        })
+    }
+}
 
-            shared_state_lock.maximized = maximized;
+unsafe fn set_maximized(
+    ns_window: id,
+    is_zoomed: bool,
+    maximized: bool,
+    shared_state: &Weak<Mutex<SharedState>>,
+) {
+    if let Some(shared_state) = shared_state.upgrade() {
+        let mut shared_state_lock =
+            SharedStateMutexGuard::new(shared_state.lock().unwrap(), "set_maximized");
 
-            if shared_state_lock.fullscreen.is_some() {
-                // Handle it in window_did_exit_fullscreen
-                return;
-            }
+        if !shared_state_lock.update_maximized(maximized, is_zoomed, NSWindow::frame(ns_window)) {
+            // Handle it in window_did_exit_fullscreen
+            return;
+        }
 
-            if ns_window
-                .styleMask()
-                .contains(NSWindowStyleMask::NSResizableWindowMask)
-            {
-                // Just use the native zoom if resizable
-                ns_window.zoom_(nil);
+        if ns_window
+            .styleMask()
+            .contains(NSWindowStyleMask::NSResizableWindowMask)
+        {
+            // Just use the native zoom if resizable
+            ns_window.zoom_(nil);
+        } else {
+            // if it's not resizable, we set the frame directly
+            let new_rect = if maximized {
+                let screen = NSScreen::mainScreen(nil);
+                NSScreen::visibleFrame(screen)
             } else {
-                // if it's not resizable, we set the frame directly
-                let new_rect = if maximized {
-                    let screen = NSScreen::mainScreen(nil);
-                    NSScreen::visibleFrame(screen)
-                } else {
-                    shared_state_lock.saved_standard_frame()
-                };
-                ns_window.setFrame_display_(new_rect, Bool::NO.as_raw());
-            }
+                shared_state_lock.saved_standard_frame()
+            };
+            ns_window.setFrame_display_(new_rect, Bool::NO.as_raw());
         }
-    });
+    }
 }
 
 // `orderOut:` isn't thread-safe. Calling it from another thread actually works,
@@ -216,11 +449,297 @@ pub unsafe fn make_key_and_order_front_async(ns_window: id) {
 pub unsafe fn set_title_async(ns_window: id, title: String) {
     let ns_window = MainThreadSafe(ns_window);
     Queue::main().exec_async(move || {
-        let title = IdRef::new(NSString::alloc(nil).init_str(&title));
-        ns_window.setTitle_(*title);
+        let title = super::ns_string_id_ref(&title);
+        // AppKit may reset the line break mode of the title when it changes.
+        let title_field = title_text_field(*ns_window);
+        if title_field == nil {
+            ns_window.setTitle_(*title);
+        } else {
+            let line_break_mode: NSUInteger = msg_send![title_field, lineBreakMode];
+            ns_window.setTitle_(*title);
+            let _: () = msg_send![title_field, setLineBreakMode: line_break_mode];
+        }
+        update_windows_menu_item(*ns_window, *title);
     });
 }
 
+pub unsafe fn invalidate_character_coordinates_async(ns_view: id) {
+    let ns_view = MainThreadSafe(ns_view);
+    Queue::main().exec_async(move || {
+        view::invalidate_character_coordinates(*ns_view);
+    });
+}
+
+pub unsafe fn set_accessibility_label_async(ns_window: id, label: String) {
+    let ns_window = MainThreadSafe(ns_window);
+    Queue::main().exec_async(move || {
+        let label = IdRef::new(NSString::alloc(nil).init_str(&label));
+        let _: () = msg_send![*ns_window, setAccessibilityLabel: *label];
+    });
+}
+
+// Views aren't accessibility elements by default, and are skipped by
+// VoiceOver, so the view is made one to be announced with its role.
+pub unsafe fn set_accessibility_role_async(ns_view: id, role: String) {
+    let ns_view = MainThreadSafe(ns_view);
+    Queue::main().exec_async(move || {
+        let role = IdRef::new(NSString::alloc(nil).init_str(&role));
+        let _: () = msg_send![*ns_view, setAccessibilityElement: true];
+        let _: () = msg_send![*ns_view, setAccessibilityRole: *role];
+    });
+}
+
+// AppKit doesn't always refresh the entry of the window in the Window menu,
+// which the Dock menu also lists, until the window is ordered again. Windows
+// that aren't listed are left alone, as this would add them to the menu.
+unsafe fn update_windows_menu_item(ns_window: id, title: id) {
+    let is_excluded: bool = msg_send![ns_window, isExcludedFromWindowsMenu];
+    let is_visible: bool = msg_send![ns_window, isVisible];
+    let is_miniaturized: bool = msg_send![ns_window, isMiniaturized];
+    if !is_excluded && (is_visible || is_miniaturized) {
+        let _: () = msg_send![NSApp(), changeWindowsItem: ns_window, title: title, filename: false];
+    }
+}
+
+pub unsafe fn set_title_line_break_mode_async(ns_window: id, line_break_mode: NSUInteger) {
+    let ns_window = MainThreadSafe(ns_window);
+    Queue::main().exec_async(move || {
+        let title_field = title_text_field(*ns_window);
+        if title_field != nil {
+            let _: () = msg_send![title_field, setLineBreakMode: line_break_mode];
+        }
+    });
+}
+
+// AppKit doesn't expose the text field showing the title, so look for it next
+// to the titlebar buttons. Returns `nil` if the window has no titlebar.
+unsafe fn title_text_field(ns_window: id) -> id {
+    let close_button = ns_window.standardWindowButton_(NSWindowButton::NSWindowCloseButton);
+    if close_button == nil {
+        return nil;
+    }
+    let titlebar_view: id = msg_send![close_button, superview];
+    let subviews: id = msg_send![titlebar_view, subviews];
+    let count: NSUInteger = msg_send![subviews, count];
+    for i in 0..count {
+        let subview: id = msg_send![subviews, objectAtIndex: i];
+        let is_text_field: Bool = msg_send![subview, isKindOfClass: class!(NSTextField)];
+        if is_text_field.as_bool() {
+            return subview;
+        }
+    }
+    nil
+}
+
+// Views may only be accessed from the main thread.
+pub unsafe fn set_accent_color_async(ns_view: id, color: Option<(f64, f64, f64, f64)>) {
+    let ns_view = MainThreadSafe(ns_view);
+    Queue::main().exec_async(move || {
+        // `nil` restores the system accent color.
+        let ns_color: id = match color {
+            Some((red, green, blue, alpha)) => msg_send![
+                class!(NSColor),
+                colorWithSRGBRed: red as CGFloat,
+                green: green as CGFloat,
+                blue: blue as CGFloat,
+                alpha: alpha as CGFloat,
+            ],
+            None => nil,
+        };
+        set_content_tint_color(*ns_view, ns_color);
+    });
+}
+
+unsafe fn set_content_tint_color(ns_view: id, ns_color: id) {
+    // Only some controls (e.g. `NSButton` and `NSImageView`) have a content
+    // tint color, and only on macOS 10.14 and later.
+    let responds: Bool = msg_send![ns_view, respondsToSelector: sel!(setContentTintColor:)];
+    if responds.as_bool() {
+        let _: () = msg_send![ns_view, setContentTintColor: ns_color];
+    }
+    let subviews: id = msg_send![ns_view, subviews];
+    let count: NSUInteger = msg_send![subviews, count];
+    for i in 0..count {
+        let subview: id = msg_send![subviews, objectAtIndex: i];
+        set_content_tint_color(subview, ns_color);
+    }
+}
+
+// Views may only be accessed from the main thread.
+pub unsafe fn set_visual_effect_async(
+    ns_window: id,
+    material: Option<ffi::NSVisualEffectMaterial>,
+    blending_mode: ffi::NSVisualEffectBlendingMode,
+) {
+    let ns_window = MainThreadSafe(ns_window);
+    Queue::main().exec_async(move || {
+        // The effect view is placed in the window's frame view, behind the
+        // content view, rather than in the content view, where it would be
+        // drawn over the content.
+        let content_view: id = msg_send![*ns_window, contentView];
+        let frame_view: id = msg_send![content_view, superview];
+        let effect_view = visual_effect_view(frame_view);
+        let material = match material {
+            Some(material) => material,
+            None => {
+                if effect_view != nil {
+                    let _: () = msg_send![effect_view, removeFromSuperview];
+                }
+                return;
+            }
+        };
+
+        let effect_view = if effect_view != nil {
+            effect_view
+        } else {
+            let bounds: NSRect = msg_send![frame_view, bounds];
+            let effect_view: id = msg_send![class!(NSVisualEffectView), alloc];
+            let effect_view = IdRef::new(msg_send![effect_view, initWithFrame: bounds]);
+            let _: () = msg_send![
+                *effect_view,
+                setAutoresizingMask: (NSViewWidthSizable | NSViewHeightSizable) as NSUInteger
+            ];
+            let _: () = msg_send![
+                frame_view,
+                addSubview: *effect_view,
+                positioned: NSWindowOrderingMode::NSWindowBelow.bits(),
+                relativeTo: content_view,
+            ];
+            // Retained by the frame view.
+            *effect_view
+        };
+
+        // The newer materials are only available on macOS 10.14 and later,
+        // which is also when `NSApplication` gained `effectiveAppearance`.
+        let is_mojave: bool = msg_send![NSApp(), respondsToSelector: sel!(effectiveAppearance)];
+        let material = if material >= ffi::NSVisualEffectMaterialHeaderView && !is_mojave {
+            ffi::NSVisualEffectMaterialAppearanceBased
+        } else {
+            material
+        };
+        let _: () = msg_send![effect_view, setMaterial: material];
+        let _: () = msg_send![effect_view, setBlendingMode: blending_mode];
+        update_visual_effect_appearance(*ns_window);
+    });
+}
+
+// Appearances may only be changed from the main thread.
+pub unsafe fn set_appearance_async(ns_window: id, theme: Option<Theme>) {
+    let ns_window = MainThreadSafe(ns_window);
+    Queue::main().exec_async(move || {
+        let ns_window = ns_window.into_inner();
+        let appearance: id = match theme {
+            Some(theme) => {
+                let name = match theme {
+                    Theme::Light => "NSAppearanceNameAqua",
+                    Theme::Dark => "NSAppearanceNameDarkAqua",
+                };
+                let name = super::ns_string_id_ref(name);
+                // `nil` for the dark appearance before macOS 10.14.
+                let appearance: id = msg_send![class!(NSAppearance), appearanceNamed: *name];
+                if appearance == nil {
+                    return;
+                }
+                appearance
+            }
+            None => nil,
+        };
+        let _: () = msg_send![ns_window, setAppearance: appearance];
+    });
+}
+
+/// Applies the appearance of the window to the effect view of the blur, if
+/// any, which otherwise keeps drawing with the appearance that it was created
+/// with. Called whenever the appearance of the content view changes, see
+/// `viewDidChangeEffectiveAppearance`.
+///
+/// Must be called on the main thread.
+pub unsafe fn update_visual_effect_appearance(ns_window: id) {
+    // `effectiveAppearance` is only available on macOS 10.14 and later, before
+    // which there's only the one appearance.
+    let responds: bool = msg_send![ns_window, respondsToSelector: sel!(effectiveAppearance)];
+    if !responds {
+        return;
+    }
+    let content_view: id = msg_send![ns_window, contentView];
+    let frame_view: id = msg_send![content_view, superview];
+    let effect_view = visual_effect_view(frame_view);
+    if effect_view != nil {
+        let appearance: id = msg_send![ns_window, effectiveAppearance];
+        let _: () = msg_send![effect_view, setAppearance: appearance];
+        let _: () = msg_send![effect_view, setNeedsDisplay: true];
+    }
+}
+
+unsafe fn visual_effect_view(frame_view: id) -> id {
+    let subviews: id = msg_send![frame_view, subviews];
+    let count: NSUInteger = msg_send![subviews, count];
+    for i in 0..count {
+        let subview: id = msg_send![subviews, objectAtIndex: i];
+        let is_effect_view: bool = msg_send![subview, isKindOfClass: class!(NSVisualEffectView)];
+        if is_effect_view {
+            return subview;
+        }
+    }
+    nil
+}
+
+// `NSDockTile` isn't thread-safe, and neither are the views that we draw into it.
+pub unsafe fn set_dock_progress_async(progress: Option<f64>) {
+    Queue::main().exec_async(move || {
+        let dock_tile: id = msg_send![NSApp(), dockTile];
+        match progress {
+            None => {
+                // Restores the default tile, i.e. just the application icon.
+                let _: () = msg_send![dock_tile, setContentView: nil];
+            }
+            Some(progress) => {
+                let mut content_view: id = msg_send![dock_tile, contentView];
+                if content_view == nil {
+                    content_view = new_dock_progress_view(dock_tile);
+                    let _: () = msg_send![dock_tile, setContentView: content_view];
+                    let _: () = msg_send![content_view, release];
+                }
+                // The progress indicator is the only subview of the icon view.
+                let subviews: id = msg_send![content_view, subviews];
+                let indicator: id = msg_send![subviews, firstObject];
+                let _: () = msg_send![indicator, setDoubleValue: progress];
+            }
+        }
+        let _: () = msg_send![dock_tile, display];
+    });
+}
+
+// Creates an image view showing the application icon, with a progress bar
+// along its bottom edge. The returned view is retained.
+unsafe fn new_dock_progress_view(dock_tile: id) -> id {
+    let size: NSSize = msg_send![dock_tile, size];
+
+    let image_view: id = msg_send![class!(NSImageView), alloc];
+    let image_view: id = msg_send![
+        image_view,
+        initWithFrame: NSRect::new(NSPoint::new(0.0, 0.0), size),
+    ];
+    let icon: id = msg_send![NSApp(), applicationIconImage];
+    let _: () = msg_send![image_view, setImage: icon];
+
+    let bar_frame = NSRect::new(
+        NSPoint::new(size.width * 0.1, 0.0),
+        NSSize::new(size.width * 0.8, size.height * 0.15),
+    );
+    let indicator: id = msg_send![class!(NSProgressIndicator), alloc];
+    let indicator: id = msg_send![indicator, initWithFrame: bar_frame];
+    // `NSProgressIndicatorStyleBar`
+    let _: () = msg_send![indicator, setStyle: 0 as NSUInteger];
+    let _: () = msg_send![indicator, setIndeterminate: false];
+    let _: () = msg_send![indicator, setMinValue: 0.0f64];
+    let _: () = msg_send![indicator, setMaxValue: 1.0f64];
+    let _: () = msg_send![image_view, addSubview: indicator];
+    let _: () = msg_send![indicator, release];
+
+    image_view
+}
+
 // `close:` is thread-safe, but we want the event to be triggered from the main
 // thread. Though, it's a good idea to look into that more...
 //
@@ -230,6 +749,8 @@ pub unsafe fn close_async(ns_window: IdRef) {
     let ns_window = MainThreadSafe(ns_window);
     Queue::main().exec_async(move || {
         autoreleasepool(move |_| {
+            // Closing a sheet without ending it would leave its parent blocked.
+            super::end_sheet(**ns_window);
             ns_window.close();
         });
     });
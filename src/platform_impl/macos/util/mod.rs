@@ -3,18 +3,20 @@ mod r#async;
 pub use self::r#async::*;
 
 use std::ops::{BitAnd, Deref};
-use std::os::raw::c_uchar;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use cocoa::{
     appkit::{CGFloat, NSApp, NSWindowStyleMask},
     base::{id, nil},
-    foundation::{NSPoint, NSRect, NSString},
+    foundation::{NSPoint, NSRect, NSSize, NSString},
 };
-use core_graphics::display::CGDisplay;
-use objc2::foundation::{NSRange, NSUInteger};
+use core_graphics::display::CGDisplayBounds;
+use objc2::foundation::{NSInteger, NSRange, NSUInteger};
 
 use crate::dpi::LogicalPosition;
-use crate::platform_impl::platform::ffi;
+use crate::icon::RgbaIcon;
+use crate::platform_impl::platform::{ffi, monitor};
 
 // Replace with `!` once stable
 #[derive(Debug)]
@@ -27,6 +29,23 @@ where
     bitset & flag == flag
 }
 
+// Whether scale factors are reported as-is, see `EventLoopBuilderExtMacOS::with_dpi_aware`.
+static DPI_AWARE: AtomicBool = AtomicBool::new(true);
+
+pub fn set_dpi_aware(dpi_aware: bool) {
+    DPI_AWARE.store(dpi_aware, Ordering::Relaxed);
+}
+
+/// Converts a backing scale factor to the scale factor that winit reports, which
+/// is always `1.0` when the application isn't DPI aware.
+pub fn scale_factor(backing_scale_factor: CGFloat) -> f64 {
+    if DPI_AWARE.load(Ordering::Relaxed) {
+        backing_scale_factor as f64
+    } else {
+        1.0
+    }
+}
+
 pub const EMPTY_RANGE: NSRange = NSRange {
     location: ffi::NSNotFound as NSUInteger,
     length: 0,
@@ -108,27 +127,110 @@ impl Drop for TraceGuard {
     }
 }
 
-// For consistency with other platforms, this will...
-// 1. translate the bottom-left window corner into the top-left window corner
-// 2. translate the coordinate from a bottom-left origin coordinate system to a top-left one
-pub fn bottom_left_to_top_left(rect: NSRect) -> f64 {
-    CGDisplay::main().pixels_high() as f64 - (rect.origin.y + rect.size.height) as f64
+// Screen coordinates
+//
+// Winit positions windows, the cursor and monitors in screen coordinates whose
+// origin is the top-left corner of the primary display, with y increasing
+// downwards. These are the same as Quartz's display coordinates, as used by
+// `CGDisplayBounds` and `CGWarpMouseCursorPosition`. AppKit's screen coordinates
+// have their origin at the bottom-left corner of the primary display instead,
+// with y increasing upwards. All conversions between the two go through
+// `top_left` and `ns_point`, which are inverses of each other.
+
+// The height of the primary display, i.e. the one with the menu bar, which
+// both coordinate systems are relative to.
+// The bounds of a rotated display are rotated too, so this is the height of
+// the primary display as it's laid out, e.g. the long side of a portrait one.
+fn primary_display_height() -> f64 {
+    unsafe { CGDisplayBounds(monitor::primary_display_id()) }
+        .size
+        .height as f64
 }
 
-/// Converts from winit screen-coordinates to macOS screen-coordinates.
-/// Winit: top-left is (0, 0) and y increasing downwards
-/// macOS: bottom-left is (0, 0) and y increasing upwards
-pub fn window_position(position: LogicalPosition<f64>) -> NSPoint {
+/// Returns the position of the top-left corner of `rect`, which is in AppKit
+/// screen coordinates, in winit screen coordinates.
+pub fn top_left(rect: NSRect) -> LogicalPosition<f64> {
+    LogicalPosition::new(
+        rect.origin.x as f64,
+        primary_display_height() - (rect.origin.y + rect.size.height) as f64,
+    )
+}
+
+/// Converts `position` from winit screen coordinates to AppKit screen
+/// coordinates.
+pub fn ns_point(position: LogicalPosition<f64>) -> NSPoint {
     NSPoint::new(
         position.x as CGFloat,
-        CGDisplay::main().pixels_high() as CGFloat - position.y as CGFloat,
+        (primary_display_height() - position.y) as CGFloat,
     )
 }
 
+/// Returns the accent color chosen in System Settings as `(red, green, blue,
+/// alpha)` in the sRGB color space.
+pub unsafe fn system_accent_color() -> (f64, f64, f64, f64) {
+    let responds: bool = msg_send![class!(NSColor), respondsToSelector: sel!(controlAccentColor)];
+    // There's no accent color before macOS 10.14, only the highlight color,
+    // which follows the blue accent by default.
+    let color: id = if responds {
+        msg_send![class!(NSColor), controlAccentColor]
+    } else {
+        msg_send![class!(NSColor), alternateSelectedControlColor]
+    };
+    let color_space: id = msg_send![class!(NSColorSpace), sRGBColorSpace];
+    let color: id = msg_send![color, colorUsingColorSpace: color_space];
+    if color == nil {
+        return (0.0, 0.0, 0.0, 1.0);
+    }
+    let (mut red, mut green, mut blue, mut alpha): (CGFloat, CGFloat, CGFloat, CGFloat) =
+        (0.0, 0.0, 0.0, 0.0);
+    let _: () = msg_send![
+        color,
+        getRed: &mut red as *mut CGFloat
+        green: &mut green as *mut CGFloat
+        blue: &mut blue as *mut CGFloat
+        alpha: &mut alpha as *mut CGFloat
+    ];
+    (red as f64, green as f64, blue as f64, alpha as f64)
+}
+
 pub unsafe fn ns_string_id_ref(s: &str) -> IdRef {
     IdRef::new(NSString::alloc(nil).init_str(s))
 }
 
+/// Creates an `NSImage` from RGBA pixels, sized in points for the given scale
+/// factor so that it's drawn at its native resolution.
+pub unsafe fn ns_image_from_rgba(icon: &RgbaIcon, scale_factor: f64) -> IdRef {
+    let width = icon.width as NSInteger;
+    let height = icon.height as NSInteger;
+    let color_space = ns_string_id_ref("NSDeviceRGBColorSpace");
+    let bitmap: id = msg_send![class!(NSBitmapImageRep), alloc];
+    let bitmap = IdRef::new(msg_send![
+        bitmap,
+        initWithBitmapDataPlanes: ptr::null_mut::<*mut u8>(),
+        pixelsWide: width,
+        pixelsHigh: height,
+        bitsPerSample: 8 as NSInteger,
+        samplesPerPixel: 4 as NSInteger,
+        hasAlpha: true,
+        isPlanar: false,
+        colorSpaceName: *color_space,
+        bitmapFormat: ffi::NSBitmapFormatAlphaNonpremultiplied,
+        bytesPerRow: width * 4,
+        bitsPerPixel: 32 as NSInteger,
+    ]);
+    let data: *mut u8 = msg_send![*bitmap, bitmapData];
+    ptr::copy_nonoverlapping(icon.rgba.as_ptr(), data, icon.rgba.len());
+
+    let size = NSSize::new(
+        (icon.width as f64 / scale_factor) as CGFloat,
+        (icon.height as f64 / scale_factor) as CGFloat,
+    );
+    let image: id = msg_send![class!(NSImage), alloc];
+    let image = IdRef::new(msg_send![image, initWithSize: size]);
+    let _: () = msg_send![*image, addRepresentation: *bitmap];
+    image
+}
+
 #[allow(dead_code)] // In case we want to use this function in the future
 pub unsafe fn app_name() -> Option<id> {
     let bundle: id = msg_send![class!(NSBundle), mainBundle];
@@ -147,6 +249,120 @@ pub unsafe fn open_emoji_picker() {
     let _: () = msg_send![NSApp(), orderFrontCharacterPalette: nil];
 }
 
+/// Hides or shows the standard titlebar buttons: the close, minimize and zoom
+/// buttons (the "traffic lights"), and the full screen button.
+pub unsafe fn set_titlebar_buttons_hidden(window: id, hidden: bool) {
+    use cocoa::appkit::{NSWindow, NSWindowButton};
+
+    for titlebar_button in &[
+        NSWindowButton::NSWindowFullScreenButton,
+        NSWindowButton::NSWindowMiniaturizeButton,
+        NSWindowButton::NSWindowCloseButton,
+        NSWindowButton::NSWindowZoomButton,
+    ] {
+        let button = window.standardWindowButton_(*titlebar_button);
+        let _: () = msg_send![button, setHidden: hidden];
+    }
+}
+
+/// Sets the level of `window`, and raises its child windows to that level if
+/// they're below it.
+///
+/// AppKit only orders child windows above their parent among windows of the
+/// same level, so a child, e.g. an inspector panel, would otherwise go behind
+/// its parent once the parent is raised. Children that are above the new
+/// level are left there.
+pub unsafe fn set_level(window: id, level: NSInteger) {
+    let _: () = msg_send![window, setLevel: level];
+    let children: id = msg_send![window, childWindows];
+    let count: NSUInteger = msg_send![children, count];
+    for i in 0..count {
+        let child: id = msg_send![children, objectAtIndex: i];
+        let child_level: NSInteger = msg_send![child, level];
+        if child_level < level {
+            set_level(child, level);
+        }
+    }
+}
+
+/// Enables the zoom button, which also enters fullscreen, only while the window
+/// is resizable, or in fullscreen so that it can still be exited.
+///
+/// AppKit leaves the button enabled when a window is made non-resizable after
+/// its creation, and such a window enters a fullscreen that it doesn't fill.
+pub unsafe fn update_zoom_button(window: id) {
+    use cocoa::appkit::{NSWindow, NSWindowButton};
+
+    let enabled = zoom_button_enabled(window.styleMask());
+    let button = window.standardWindowButton_(NSWindowButton::NSWindowZoomButton);
+    if button != nil {
+        let _: () = msg_send![button, setEnabled: enabled];
+    }
+}
+
+fn zoom_button_enabled(mask: NSWindowStyleMask) -> bool {
+    mask.intersects(
+        NSWindowStyleMask::NSResizableWindowMask | NSWindowStyleMask::NSFullScreenWindowMask,
+    )
+}
+
+/// Sets the scale of the contents of the view's layer, if it has one.
+pub unsafe fn set_contents_scale(ns_view: id, scale: f64) {
+    let layer: id = msg_send![ns_view, layer];
+    if layer != nil {
+        let _: () = msg_send![layer, setContentsScale: scale as CGFloat];
+    }
+}
+
+/// Rounds the corners of the view's layer, and reshapes the window's shadow to
+/// match.
+pub unsafe fn set_corner_radius(ns_window: id, ns_view: id, radius: Option<f64>) {
+    let (radius, masks_to_bounds) = corner_radius_of_layer(radius);
+    if masks_to_bounds {
+        let _: () = msg_send![ns_view, setWantsLayer: true];
+    }
+    let layer: id = msg_send![ns_view, layer];
+    if layer != nil {
+        let _: () = msg_send![layer, setCornerRadius: radius];
+        let _: () = msg_send![layer, setMasksToBounds: masks_to_bounds];
+    }
+    // The shadow of a non-opaque window is computed from its content, but isn't
+    // recomputed until invalidated.
+    let _: () = msg_send![ns_window, invalidateShadow];
+}
+
+/// Ends the sheet that the window is presented as, if any, which hides it.
+pub unsafe fn end_sheet(ns_window: id) {
+    let parent: id = msg_send![ns_window, sheetParent];
+    if parent != nil {
+        let _: () = msg_send![parent, endSheet: ns_window];
+    }
+}
+
+/// Centers the window within the visible frame of the screen, i.e. without the
+/// menu bar and the Dock. A window that doesn't fit is aligned with the left or
+/// top edge instead, so that its titlebar stays on the screen.
+pub unsafe fn center_on_screen(ns_window: id, ns_screen: id) {
+    use cocoa::appkit::{NSScreen, NSWindow};
+
+    let screen_frame = NSScreen::visibleFrame(ns_screen);
+    let frame = NSWindow::frame(ns_window);
+    let x = screen_frame.origin.x + ((screen_frame.size.width - frame.size.width) / 2.0).max(0.0);
+    // The origin point of a rectangle is at its bottom left in Cocoa, so the
+    // top edges are aligned by moving the window down.
+    let y = screen_frame.origin.y + screen_frame.size.height
+        - frame.size.height
+        - ((screen_frame.size.height - frame.size.height) / 2.0).max(0.0);
+    ns_window.setFrameOrigin_(NSPoint::new(x, y));
+}
+
+// Returns the corner radius of the layer, and whether it masks its contents to
+// its bounds, which is what clips them to the rounded corners.
+fn corner_radius_of_layer(radius: Option<f64>) -> (CGFloat, bool) {
+    let radius = radius.unwrap_or(0.0).max(0.0);
+    (radius as CGFloat, radius > 0.0)
+}
+
 pub unsafe fn toggle_style_mask(window: id, view: id, mask: NSWindowStyleMask, on: bool) {
     use cocoa::appkit::NSWindow;
 
@@ -156,13 +372,14 @@ pub unsafe fn toggle_style_mask(window: id, view: id, mask: NSWindowStyleMask, o
     } else {
         window.setStyleMask_(current_style_mask & (!mask));
     }
+    update_zoom_button(window);
 
     // If we don't do this, key handling will break. Therefore, never call `setStyleMask` directly!
     window.makeFirstResponder_(view);
 }
 
-/// For invalid utf8 sequences potentially returned by `UTF8String`,
-/// it behaves identically to `String::from_utf8_lossy`
+/// Unpaired surrogates, which `NSString` allows, are replaced with
+/// `U+FFFD REPLACEMENT CHARACTER`, as by `String::from_utf16_lossy`.
 ///
 /// Safety: Assumes that `string` is an instance of `NSAttributedString` or `NSString`
 pub unsafe fn id_to_string_lossy(string: id) -> String {
@@ -174,7 +391,95 @@ pub unsafe fn id_to_string_lossy(string: id) -> String {
         // This is already a *mut NSString
         string
     };
-    let utf8_sequence =
-        std::slice::from_raw_parts(characters.UTF8String() as *const c_uchar, characters.len());
-    String::from_utf8_lossy(utf8_sequence).into_owned()
+    // `UTF8String` returns null for strings with unpaired surrogates, e.g.
+    // while an emoji is being composed, so the UTF-16 code units are copied
+    // out instead.
+    let length: NSUInteger = msg_send![characters, length];
+    let mut utf16_sequence = vec![0u16; length];
+    let _: () = msg_send![
+        characters,
+        getCharacters: utf16_sequence.as_mut_ptr(),
+        range: NSRange::new(0, length)
+    ];
+    String::from_utf16_lossy(&utf16_sequence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_left_and_ns_point_round_trip() {
+        let height = primary_display_height();
+        for &(x, y) in &[
+            (0.0, 0.0),
+            (100.5, 200.25),
+            (-1920.0, -300.0),
+            (0.0, height),
+        ] {
+            let position = LogicalPosition::new(x, y);
+            // The top-left corner of a window whose bottom-left corner is at
+            // `ns_point(position)` minus its height.
+            let size = NSSize::new(800.0, 600.0);
+            let NSPoint { x, y } = ns_point(position);
+            let rect = NSRect::new(NSPoint::new(x, y - size.height), size);
+            assert_eq!(top_left(rect), position);
+            let point = ns_point(top_left(rect));
+            assert_eq!((point.x, point.y), (x, y));
+        }
+    }
+
+    #[test]
+    fn id_to_string_lossy_keeps_all_of_unicode() {
+        for s in ["", "winit", "日本語", "🦀 👩‍👩‍👧", "e\u{301}"] {
+            let string = unsafe { ns_string_id_ref(s) };
+            assert_eq!(unsafe { id_to_string_lossy(*string) }, s);
+        }
+    }
+
+    #[test]
+    fn id_to_string_lossy_replaces_unpaired_surrogates() {
+        // The high surrogate of "🦀", as while composing it.
+        let characters: [u16; 2] = [0x61, 0xd83e];
+        let string: id = unsafe {
+            msg_send![
+                class!(NSString),
+                stringWithCharacters: characters.as_ptr(),
+                length: characters.len() as NSUInteger
+            ]
+        };
+        assert_eq!(unsafe { id_to_string_lossy(string) }, "a\u{fffd}");
+    }
+
+    #[test]
+    fn top_left_of_the_primary_display_is_the_origin() {
+        let height = primary_display_height();
+        let rect = NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(1.0, height));
+        assert_eq!(top_left(rect), LogicalPosition::new(0.0, 0.0));
+        let point = ns_point(LogicalPosition::new(0.0, 0.0));
+        assert_eq!((point.x, point.y), (0.0, height));
+    }
+
+    #[test]
+    fn zoom_button_is_disabled_when_not_resizable() {
+        let titled = NSWindowStyleMask::NSTitledWindowMask
+            | NSWindowStyleMask::NSClosableWindowMask
+            | NSWindowStyleMask::NSMiniaturizableWindowMask;
+        assert!(!zoom_button_enabled(titled));
+        assert!(zoom_button_enabled(
+            titled | NSWindowStyleMask::NSResizableWindowMask
+        ));
+        // A non-resizable window can still exit fullscreen with the button.
+        assert!(zoom_button_enabled(
+            titled | NSWindowStyleMask::NSFullScreenWindowMask
+        ));
+    }
+
+    #[test]
+    fn corners_are_only_masked_with_a_positive_radius() {
+        assert_eq!(corner_radius_of_layer(Some(12.0)), (12.0, true));
+        assert_eq!(corner_radius_of_layer(Some(0.0)), (0.0, false));
+        assert_eq!(corner_radius_of_layer(Some(-4.0)), (0.0, false));
+        assert_eq!(corner_radius_of_layer(None), (0.0, false));
+    }
 }
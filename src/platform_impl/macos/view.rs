@@ -4,16 +4,20 @@ use std::{
     os::raw::*,
     ptr, slice, str,
     sync::{
-        atomic::{compiler_fence, Ordering},
-        Mutex,
+        atomic::{compiler_fence, AtomicBool, Ordering},
+        Arc, Mutex,
     },
 };
 
 use cocoa::{
-    appkit::{NSApp, NSEvent, NSEventModifierFlags, NSEventPhase, NSView, NSWindow},
+    appkit::{
+        self, CGFloat, NSApp, NSEvent, NSEventModifierFlags, NSEventPhase, NSEventType, NSView,
+        NSWindow,
+    },
     base::{id, nil},
     foundation::{NSPoint, NSRect, NSSize, NSString},
 };
+use core_graphics::display::CGDisplay;
 use objc2::foundation::{NSInteger, NSObject, NSRange, NSUInteger};
 use objc2::rc::{Id, Shared};
 use objc2::runtime::{Bool, Object, Sel};
@@ -21,12 +25,14 @@ use objc2::{declare_class, ClassType};
 
 use super::appkit::{NSCursor, NSResponder, NSView as NSViewClass};
 use crate::{
-    dpi::{LogicalPosition, LogicalSize},
+    dpi::{LogicalPosition, LogicalSize, PhysicalSize},
     event::{
         DeviceEvent, ElementState, Event, Ime, KeyboardInput, ModifiersState, MouseButton,
         MouseScrollDelta, TouchPhase, VirtualKeyCode, WindowEvent,
     },
+    platform::macos::KeyEquivalentPolicy,
     platform_impl::platform::{
+        app,
         app_state::AppState,
         event::{
             char_to_keycode, check_function_keys, event_mods, get_scancode, modifier_event,
@@ -42,18 +48,85 @@ use crate::{
 
 pub struct CursorState {
     pub visible: bool,
+    /// Whether the cursor is hidden because it's locked, regardless of `visible`.
+    pub(super) hidden_by_grab: bool,
     pub(super) cursor: Id<NSCursor, Shared>,
+    /// Whether this view has hidden the cursor with `[NSCursor hide]`, and so
+    /// owes exactly one `[NSCursor unhide]`.
+    hidden: bool,
 }
 
 impl Default for CursorState {
     fn default() -> Self {
         Self {
             visible: true,
+            hidden_by_grab: false,
             cursor: Default::default(),
+            hidden: false,
         }
     }
 }
 
+impl CursorState {
+    pub(super) fn is_visible(&self) -> bool {
+        cursor_is_visible(self.visible, self.hidden_by_grab)
+    }
+
+    // The cursor rect alone only takes effect on the next mouse move, and is
+    // reset whenever AppKit sets another cursor, so the cursor is also hidden
+    // explicitly while it's over the view.
+    fn set_hidden(&mut self, hidden: bool) {
+        set_cursor_hidden_with(&mut self.hidden, hidden, NSCursor::hide, NSCursor::unhide);
+    }
+
+    fn reapply_hidden(&mut self, hidden: bool) {
+        reapply_cursor_hidden_with(&mut self.hidden, hidden, NSCursor::hide, NSCursor::unhide);
+    }
+}
+
+fn cursor_is_visible(visible: bool, hidden_by_grab: bool) -> bool {
+    visible && !hidden_by_grab
+}
+
+// The cursor is only hidden while it's over the view of the key window, so
+// that it shows over the title bar and other windows.
+fn cursor_should_hide(is_visible: bool, is_key_window: bool, is_inside: bool) -> bool {
+    !is_visible && is_key_window && is_inside
+}
+
+// `hide` and `unhide` are counted app-wide, hence `hidden`, so that they're
+// only called alternately.
+fn set_cursor_hidden_with(
+    hidden: &mut bool,
+    new_hidden: bool,
+    hide: impl FnOnce(),
+    unhide: impl FnOnce(),
+) {
+    if new_hidden == *hidden {
+        return;
+    }
+    *hidden = new_hidden;
+    if new_hidden {
+        hide();
+    } else {
+        unhide();
+    }
+}
+
+// AppKit may show the cursor behind our back, e.g. during a fullscreen
+// transition, while `hidden` is still set. An extra `unhide` is harmless if
+// the cursor is already shown, and keeps `hide` and `unhide` alternating
+// otherwise.
+fn reapply_cursor_hidden_with(
+    hidden: &mut bool,
+    new_hidden: bool,
+    hide: impl Fn(),
+    unhide: impl Fn(),
+) {
+    set_cursor_hidden_with(hidden, false, &hide, &unhide);
+    set_cursor_hidden_with(hidden, new_hidden, &hide, &unhide);
+}
+
 #[derive(Debug, Eq, PartialEq)]
 enum ImeState {
     /// The IME events are disabled, so only `ReceivedCharacter` is being sent to the user.
@@ -73,6 +146,9 @@ pub(super) struct ViewState {
     ns_window: id,
     pub cursor_state: Mutex<CursorState>,
     ime_position: LogicalPosition<f64>,
+    /// Whether the input context is yet to be told that `ime_position` changed,
+    /// see `set_ime_position`.
+    ime_position_pending: AtomicBool,
     pub(super) modifiers: ModifiersState,
     tracking_rect: Option<NSInteger>,
     ime_state: ImeState,
@@ -83,14 +159,44 @@ pub(super) struct ViewState {
     /// Can be set using `set_ime_allowed`
     ime_allowed: bool,
 
+    /// True if key presses that commit text without composing are reported as
+    /// `KeyboardInput` too.
+    ///
+    /// Can be set using `set_ime_passthrough`
+    ime_passthrough: bool,
+
     /// True if the current key event should be forwarded
     /// to the application, even during IME
     forward_key_to_app: bool,
+
+    /// The distance that the mouse must move before `drag_window` drags the
+    /// window, as set by `set_drag_threshold`.
+    drag_threshold: Option<f64>,
+    /// The mouse down to drag the window with once the mouse moved past the
+    /// threshold, see `defer_drag`.
+    pending_drag: Option<IdRef>,
+
+    /// Whether key equivalents go to the menus or to the view, as set by
+    /// `set_key_equivalent_policy`.
+    key_equivalent_policy: KeyEquivalentPolicy,
+    /// Decides the policy per key equivalent instead, if set. It's cloned out
+    /// of the lock before being called, so it may replace itself.
+    key_equivalent_handler: Mutex<Option<Arc<KeyEquivalentHandler>>>,
+
+    /// Whether the window is redrawn from within `frameDidChange:`, as set by
+    /// `set_synchronous_resize`.
+    synchronous_resize: bool,
+
+    /// The rect in view coordinates, with a top-left origin, that the cursor
+    /// is kept in while the window is key, as set by `set_cursor_confine_rect`.
+    cursor_confine_rect: Option<(LogicalPosition<f64>, LogicalSize<f64>)>,
 }
 
+pub(super) type KeyEquivalentHandler = dyn Fn(&KeyboardInput) -> KeyEquivalentPolicy + Send + Sync;
+
 impl ViewState {
     fn get_scale_factor(&self) -> f64 {
-        (unsafe { NSWindow::backingScaleFactor(self.ns_window) }) as f64
+        util::scale_factor(unsafe { NSWindow::backingScaleFactor(self.ns_window) })
     }
 
     fn is_ime_enabled(&self) -> bool {
@@ -103,12 +209,20 @@ pub fn new_view(ns_window: id) -> IdRef {
         ns_window,
         cursor_state: Default::default(),
         ime_position: LogicalPosition::new(0.0, 0.0),
+        ime_position_pending: AtomicBool::new(false),
         modifiers: Default::default(),
         tracking_rect: None,
         ime_state: ImeState::Disabled,
         input_source: String::new(),
         ime_allowed: false,
+        ime_passthrough: false,
         forward_key_to_app: false,
+        drag_threshold: None,
+        pending_drag: None,
+        key_equivalent_policy: Default::default(),
+        key_equivalent_handler: Mutex::new(None),
+        synchronous_resize: false,
+        cursor_confine_rect: None,
     };
     unsafe {
         // This is free'd in `dealloc`
@@ -118,10 +232,27 @@ pub fn new_view(ns_window: id) -> IdRef {
     }
 }
 
+// Editors set the position on every caret move, so the updates are coalesced:
+// unchanged positions are dropped, and the input context is told about the
+// changes once per pass of the main run loop. It asks for the position again
+// in `firstRectForCharacterRange:actualRange:`, so the latest one always wins.
 pub unsafe fn set_ime_position(ns_view: id, position: LogicalPosition<f64>) {
     let state_ptr: *mut c_void = *(*ns_view).ivar_mut("winitState");
     let state = &mut *(state_ptr as *mut ViewState);
+    if state.ime_position == position {
+        return;
+    }
     state.ime_position = position;
+    if !state.ime_position_pending.swap(true, Ordering::AcqRel) {
+        util::invalidate_character_coordinates_async(ns_view);
+    }
+}
+
+/// Tells the input context that the IME position changed, see `set_ime_position`.
+pub(super) unsafe fn invalidate_character_coordinates(ns_view: id) {
+    let state_ptr: *mut c_void = *(*ns_view).ivar("winitState");
+    let state = &*(state_ptr as *const ViewState);
+    state.ime_position_pending.store(false, Ordering::Release);
     let input_context: id = msg_send![ns_view, inputContext];
     let _: () = msg_send![input_context, invalidateCharacterCoordinates];
 }
@@ -153,6 +284,250 @@ pub unsafe fn set_ime_allowed(ns_view: id, ime_allowed: bool) {
     }
 }
 
+pub unsafe fn ime_passthrough(ns_view: id) -> bool {
+    let state_ptr: *mut c_void = *(*ns_view).ivar("winitState");
+    let state = &*(state_ptr as *const ViewState);
+    state.ime_passthrough
+}
+
+pub unsafe fn set_ime_passthrough(ns_view: id, ime_passthrough: bool) {
+    let state_ptr: *mut c_void = *(*ns_view).ivar_mut("winitState");
+    let state = &mut *(state_ptr as *mut ViewState);
+    state.ime_passthrough = ime_passthrough;
+}
+
+pub unsafe fn key_equivalent_policy(ns_view: id) -> KeyEquivalentPolicy {
+    let state_ptr: *mut c_void = *(*ns_view).ivar("winitState");
+    let state = &*(state_ptr as *const ViewState);
+    state.key_equivalent_policy
+}
+
+pub unsafe fn set_key_equivalent_policy(ns_view: id, policy: KeyEquivalentPolicy) {
+    let state_ptr: *mut c_void = *(*ns_view).ivar_mut("winitState");
+    let state = &mut *(state_ptr as *mut ViewState);
+    state.key_equivalent_policy = policy;
+}
+
+pub unsafe fn set_key_equivalent_handler(ns_view: id, handler: Option<Arc<KeyEquivalentHandler>>) {
+    let state_ptr: *mut c_void = *(*ns_view).ivar("winitState");
+    let state = &*(state_ptr as *const ViewState);
+    *state.key_equivalent_handler.lock().unwrap() = handler;
+}
+
+// Decides whether a key equivalent goes to the menus or to the view, from the
+// handler if there's one.
+unsafe fn resolve_key_equivalent_policy(state: &ViewState, event: id) -> KeyEquivalentPolicy {
+    let handler = state.key_equivalent_handler.lock().unwrap().clone();
+    match handler {
+        Some(handler) => {
+            #[allow(deprecated)]
+            let input = KeyboardInput {
+                state: ElementState::Pressed,
+                scancode: get_scancode(event) as u32,
+                virtual_keycode: retrieve_keycode(event),
+                modifiers: event_mods(event),
+            };
+            handler(&input)
+        }
+        None => state.key_equivalent_policy,
+    }
+}
+
+pub unsafe fn synchronous_resize(ns_view: id) -> bool {
+    let state_ptr: *mut c_void = *(*ns_view).ivar("winitState");
+    let state = &*(state_ptr as *const ViewState);
+    state.synchronous_resize
+}
+
+pub unsafe fn set_synchronous_resize(ns_view: id, synchronous_resize: bool) {
+    let state_ptr: *mut c_void = *(*ns_view).ivar_mut("winitState");
+    let state = &mut *(state_ptr as *mut ViewState);
+    state.synchronous_resize = synchronous_resize;
+}
+
+pub unsafe fn set_cursor_confine_rect(
+    ns_view: id,
+    rect: Option<(LogicalPosition<f64>, LogicalSize<f64>)>,
+) {
+    let state_ptr: *mut c_void = *(*ns_view).ivar_mut("winitState");
+    let state = &mut *(state_ptr as *mut ViewState);
+    state.cursor_confine_rect = rect;
+
+    // Bring the cursor inside right away rather than on its next move.
+    let window_point: NSPoint = msg_send![state.ns_window, mouseLocationOutsideOfEventStream];
+    let view_point = ns_view.convertPoint_fromView_(window_point, nil);
+    let view_rect = NSView::frame(ns_view);
+    let position = LogicalPosition::new(
+        view_point.x as f64,
+        view_rect.size.height as f64 - view_point.y as f64,
+    );
+    confine_cursor(state, ns_view, position);
+}
+
+// The confinement rect is clipped to the view, as it's resized, and ignored
+// while it doesn't overlap it at all. The cursor is warped back to the closest
+// point of the rect whenever it's moved out of it, and that point is reported
+// as the position of the cursor.
+unsafe fn confine_cursor(
+    state: &ViewState,
+    view: id,
+    position: LogicalPosition<f64>,
+) -> LogicalPosition<f64> {
+    let (origin, size) = match state.cursor_confine_rect {
+        Some(rect) => rect,
+        None => return position,
+    };
+    let is_key_window: bool = msg_send![state.ns_window, isKeyWindow];
+    if !is_key_window {
+        return position;
+    }
+
+    let view_rect = NSView::frame(view);
+    let min_x = origin.x.max(0.0);
+    let min_y = origin.y.max(0.0);
+    let max_x = (origin.x + size.width).min(view_rect.size.width as f64);
+    let max_y = (origin.y + size.height).min(view_rect.size.height as f64);
+    if min_x > max_x || min_y > max_y {
+        return position;
+    }
+
+    let confined = LogicalPosition::new(
+        position.x.max(min_x).min(max_x),
+        position.y.max(min_y).min(max_y),
+    );
+    if confined == position {
+        return position;
+    }
+
+    // `CGWarpMouseCursorPosition` is in winit screen coordinates.
+    let view_rect_in_window: NSRect =
+        msg_send![view, convertRect: NSView::bounds(view), toView: nil];
+    let view_rect_on_screen: NSRect =
+        msg_send![state.ns_window, convertRectToScreen: view_rect_in_window];
+    let view_position = util::top_left(view_rect_on_screen);
+    let point = appkit::CGPoint {
+        x: (view_position.x + confined.x) as CGFloat,
+        y: (view_position.y + confined.y) as CGFloat,
+    };
+    if let Err(status) = CGDisplay::warp_mouse_cursor_position(point)
+        .and_then(|()| CGDisplay::associate_mouse_and_mouse_cursor_position(true))
+    {
+        warn!("Failed to confine the cursor: CGError {}", status);
+    }
+    confined
+}
+
+pub unsafe fn drag_threshold(ns_view: id) -> Option<f64> {
+    let state_ptr: *mut c_void = *(*ns_view).ivar("winitState");
+    let state = &*(state_ptr as *const ViewState);
+    state.drag_threshold
+}
+
+pub unsafe fn set_drag_threshold(ns_view: id, threshold: Option<f64>) {
+    let state_ptr: *mut c_void = *(*ns_view).ivar_mut("winitState");
+    let state = &mut *(state_ptr as *mut ViewState);
+    state.drag_threshold = threshold.filter(|threshold| *threshold > 0.0);
+    if state.drag_threshold.is_none() {
+        state.pending_drag = None;
+    }
+}
+
+/// Defers dragging the window with `event` until the mouse moves past the drag
+/// threshold, see `start_pending_drag`.
+///
+/// Returns `false` if there's no threshold, in which case the drag should
+/// start right away.
+pub unsafe fn defer_drag(ns_view: id, event: id) -> bool {
+    let state_ptr: *mut c_void = *(*ns_view).ivar_mut("winitState");
+    let state = &mut *(state_ptr as *mut ViewState);
+    if state.drag_threshold.is_none() {
+        return false;
+    }
+    state.pending_drag = Some(IdRef::retain(event));
+    true
+}
+
+/// Finalizes any in-progress composition when the window loses focus, so that
+/// the marked text isn't left dangling when focus is regained.
+pub unsafe fn focus_lost_ime(ns_view: id) {
+    let state_ptr: *mut c_void = *(*ns_view).ivar_mut("winitState");
+    let state = &mut *(state_ptr as *mut ViewState);
+    let marked_text: id = *(*ns_view).ivar("markedText");
+    if marked_text.length() > 0 {
+        // Clears the marked text, both ours and the input context's, and sends
+        // an empty `Ime::Preedit`.
+        let _: () = msg_send![ns_view, unmarkText];
+    }
+    state.ime_state = ime_state_after_focus_lost(&state.ime_state, state.ime_allowed);
+}
+
+// The composition, if any, was abandoned, and the keyboard input that follows
+// a commit is only ignored within the same key event, which won't be delivered
+// anymore.
+// The size is that of the frame, like `inner_size`, rather than of the visible
+// rect.
+fn resized_size(frame_size: NSSize, scale_factor: f64) -> PhysicalSize<u32> {
+    LogicalSize::new(frame_size.width as f64, frame_size.height as f64).to_physical(scale_factor)
+}
+
+// The position is relative to the top-left corner of the view, which isn't
+// flipped. Going through the view's own coordinate system, rather than the
+// window's content rect, keeps this correct when the view extends under the
+// titlebar, e.g. with a full-size content view.
+fn ime_point_in_view(position: LogicalPosition<f64>, view_height: CGFloat) -> NSPoint {
+    NSPoint::new(position.x as CGFloat, view_height - position.y as CGFloat)
+}
+
+fn ime_state_after_focus_lost(ime_state: &ImeState, ime_allowed: bool) -> ImeState {
+    match ime_state {
+        _ if !ime_allowed => ImeState::Disabled,
+        ImeState::Disabled => ImeState::Disabled,
+        ImeState::Enabled | ImeState::Preedit | ImeState::Commited => ImeState::Enabled,
+    }
+}
+
+/// Hides the cursor if `set_cursor_visible(false)` is in effect and the cursor
+/// is over the view of the key window, and shows it otherwise.
+///
+/// Must be called on the main thread.
+pub unsafe fn update_cursor_hidden(ns_view: id) {
+    let state_ptr: *mut c_void = *(*ns_view).ivar_mut("winitState");
+    let state = &mut *(state_ptr as *mut ViewState);
+    let hidden = cursor_should_be_hidden(state, ns_view);
+    state.cursor_state.lock().unwrap().set_hidden(hidden);
+}
+
+/// Hides the cursor again if it should be, after AppKit may have shown it
+/// behind our back, e.g. during a fullscreen transition.
+pub unsafe fn reapply_cursor_hidden(ns_view: id) {
+    let state_ptr: *mut c_void = *(*ns_view).ivar_mut("winitState");
+    let state = &mut *(state_ptr as *mut ViewState);
+    let hidden = cursor_should_be_hidden(state, ns_view);
+    state.cursor_state.lock().unwrap().reapply_hidden(hidden);
+}
+
+unsafe fn cursor_should_be_hidden(state: &ViewState, ns_view: id) -> bool {
+    let is_key_window: bool = msg_send![state.ns_window, isKeyWindow];
+    let location = NSWindow::mouseLocationOutsideOfEventStream(state.ns_window);
+    let location: NSPoint = msg_send![ns_view, convertPoint: location, fromView: nil];
+    let bounds: NSRect = msg_send![ns_view, bounds];
+    let is_inside: bool = msg_send![ns_view, mouse: location, inRect: bounds];
+    let is_visible = state.cursor_state.lock().unwrap().is_visible();
+    cursor_should_hide(is_visible, is_key_window, is_inside)
+}
+
+/// Restores the IME state from the `ime_allowed` flag when the window gains focus.
+pub unsafe fn focus_gained_ime(ns_view: id) {
+    let state_ptr: *mut c_void = *(*ns_view).ivar_mut("winitState");
+    let state = &mut *(state_ptr as *mut ViewState);
+    if !state.ime_allowed {
+        return;
+    }
+    // The candidate window may have been positioned for another window in the meantime.
+    let input_context: id = msg_send![ns_view, inputContext];
+    let _: () = msg_send![input_context, invalidateCharacterCoordinates];
+}
+
 fn get_characters(event: id, ignore_modifiers: bool) -> String {
     unsafe {
         let characters: id = if ignore_modifiers {
@@ -184,7 +559,8 @@ fn is_corporate_character(c: char) -> bool {
     )
 }
 
-// Retrieves a layout-independent keycode given an event.
+// Retrieves the keycode of the character that the key produces with the active
+// layout, so this does depend on the layout, unlike the scancode.
 fn retrieve_keycode(event: id) -> Option<VirtualKeyCode> {
     #[inline]
     fn get_code(ev: id, raw: bool) -> Option<VirtualKeyCode> {
@@ -242,6 +618,28 @@ fn mouse_click(this: &Object, event: id, button: MouseButton, button_state: Elem
     }
 }
 
+// Drags the window with the mouse down deferred by `defer_drag`, once the mouse
+// moved far enough from it. Since the drag starts from the mouse down, the
+// window catches up with the mouse.
+fn start_pending_drag(this: &Object, event: id) {
+    unsafe {
+        let state_ptr: *mut c_void = *this.ivar("winitState");
+        let state = &mut *(state_ptr as *mut ViewState);
+
+        let (threshold, pending_drag) = match (state.drag_threshold, &state.pending_drag) {
+            (Some(threshold), Some(pending_drag)) => (threshold, **pending_drag),
+            _ => return,
+        };
+        let start = pending_drag.locationInWindow();
+        let location = event.locationInWindow();
+        if ((location.x - start.x).hypot(location.y - start.y) as f64) < threshold {
+            return;
+        }
+        state.pending_drag = None;
+        let _: () = msg_send![state.ns_window, performWindowDragWithEvent: pending_drag];
+    }
+}
+
 fn mouse_motion(this: &Object, event: id) {
     unsafe {
         let state_ptr: *mut c_void = *this.ivar("winitState");
@@ -254,10 +652,14 @@ fn mouse_motion(this: &Object, event: id) {
         let view_point = view.convertPoint_fromView_(window_point, nil);
         let view_rect = NSView::frame(view);
 
-        if view_point.x.is_sign_negative()
-            || view_point.y.is_sign_negative()
-            || view_point.x > view_rect.size.width
-            || view_point.y > view_rect.size.height
+        let x = view_point.x as f64;
+        let y = view_rect.size.height as f64 - view_point.y as f64;
+        let logical_position = confine_cursor(state, view, LogicalPosition::new(x, y));
+
+        if logical_position.x.is_sign_negative()
+            || logical_position.y.is_sign_negative()
+            || logical_position.x > view_rect.size.width as f64
+            || logical_position.y > view_rect.size.height as f64
         {
             let mouse_buttons_down: NSUInteger = msg_send![class!(NSEvent), pressedMouseButtons];
             if mouse_buttons_down == 0 {
@@ -266,10 +668,6 @@ fn mouse_motion(this: &Object, event: id) {
             }
         }
 
-        let x = view_point.x as f64;
-        let y = view_rect.size.height as f64 - view_point.y as f64;
-        let logical_position = LogicalPosition::new(x, y);
-
         update_potentially_stale_modifiers(state, event);
 
         let window_event = Event::WindowEvent {
@@ -305,7 +703,9 @@ declare_class!(
                 let marked_text: id = *self.ivar("markedText");
                 let _: () = msg_send![marked_text, release];
                 let state: *mut c_void = *self.ivar("winitState");
-                drop(Box::from_raw(state as *mut ViewState));
+                let state = Box::from_raw(state as *mut ViewState);
+                state.cursor_state.lock().unwrap().set_hidden(false);
+                drop(state);
             }
         }
 
@@ -387,13 +787,19 @@ declare_class!(
                 // Emit resize event here rather than from windowDidResize because:
                 // 1. When a new window is created as a tab, the frame size may change without a window resize occurring.
                 // 2. Even when a window resize does occur on a new tabbed window, it contains the wrong size (includes tab height).
-                let logical_size =
-                    LogicalSize::new(rect.size.width as f64, rect.size.height as f64);
-                let size = logical_size.to_physical::<u32>(state.get_scale_factor());
+                let size = resized_size(self.frame().size, state.get_scale_factor());
+                let window_id = WindowId(get_window_id(state.ns_window));
                 AppState::queue_event(EventWrapper::StaticEvent(Event::WindowEvent {
-                    window_id: WindowId(get_window_id(state.ns_window)),
+                    window_id,
                     event: WindowEvent::Resized(size),
                 }));
+
+                // The window is displayed at its new size once this returns, so
+                // redraw it now rather than on the next iteration of the event
+                // loop, which would show stale contents in the meantime.
+                if state.synchronous_resize {
+                    AppState::handle_synchronous_resize(window_id);
+                }
             }
         }
 
@@ -410,6 +816,18 @@ declare_class!(
             }
         }
 
+        // The appearance changes with `set_theme`, or with the system's
+        // appearance while the window follows it.
+        #[sel(viewDidChangeEffectiveAppearance)]
+        fn view_did_change_effective_appearance(&self) {
+            trace_scope!("viewDidChangeEffectiveAppearance");
+            unsafe {
+                let state_ptr: *mut c_void = *self.ivar("winitState");
+                let state = &mut *(state_ptr as *mut ViewState);
+                util::update_visual_effect_appearance(state.ns_window);
+            }
+        }
+
         #[sel(acceptsFirstResponder)]
         fn accepts_first_responder(&self) -> bool {
             trace_scope!("acceptsFirstResponder");
@@ -435,7 +853,7 @@ declare_class!(
 
             let bounds = self.bounds();
             let cursor_state = state.cursor_state.lock().unwrap();
-            if cursor_state.visible {
+            if cursor_state.is_visible() {
                 self.addCursorRect(bounds, &cursor_state.cursor);
             } else {
                 self.addCursorRect(bounds, &NSCursor::invisible());
@@ -591,19 +1009,15 @@ declare_class!(
             unsafe {
                 let state_ptr: *mut c_void = *self.ivar("winitState");
                 let state = &mut *(state_ptr as *mut ViewState);
-                let content_rect = NSWindow::contentRectForFrameRect_(
-                    state.ns_window,
-                    NSWindow::frame(state.ns_window),
-                );
-                let base_x = content_rect.origin.x as f64;
-                let base_y = (content_rect.origin.y + content_rect.size.height) as f64;
-                let x = base_x + state.ime_position.x;
-                let y = base_y - state.ime_position.y;
+                let bounds: NSRect = msg_send![self, bounds];
+                let point = ime_point_in_view(state.ime_position, bounds.size.height);
+                let point: NSPoint = msg_send![self, convertPoint: point, toView: nil];
                 // This is not ideal: We _should_ return a different position based on
                 // the currently selected character (which varies depending on the type
                 // and size of the character), but in the current `winit` API there is
                 // no way to express this. Same goes for the `NSSize`.
-                NSRect::new(NSPoint::new(x as _, y as _), NSSize::new(0.0, 0.0))
+                let rect = NSRect::new(point, NSSize::new(0.0, 0.0));
+                msg_send![state.ns_window, convertRectToScreen: rect]
             }
         }
 
@@ -713,9 +1127,11 @@ declare_class!(
 
                 update_potentially_stale_modifiers(state, event);
 
-                let ime_related = was_in_preedit || now_in_preedit || text_commited;
+                let composing = was_in_preedit || now_in_preedit;
+                let ime_related = composing || text_commited;
+                let passthrough = state.ime_passthrough && !composing;
 
-                if !ime_related || state.forward_key_to_app || !state.ime_allowed {
+                if !ime_related || passthrough || state.forward_key_to_app || !state.ime_allowed {
                     #[allow(deprecated)]
                     let window_event = Event::WindowEvent {
                         window_id,
@@ -733,11 +1149,16 @@ declare_class!(
 
                     AppState::queue_event(EventWrapper::StaticEvent(window_event));
 
-                    for character in characters.chars().filter(|c| !is_corporate_character(*c)) {
-                        AppState::queue_event(EventWrapper::StaticEvent(Event::WindowEvent {
-                            window_id,
-                            event: WindowEvent::ReceivedCharacter(character),
-                        }));
+                    // With passthrough, the committed text was already sent as
+                    // `Ime::Commit`.
+                    if !(passthrough && text_commited) {
+                        for character in characters.chars().filter(|c| !is_corporate_character(*c))
+                        {
+                            AppState::queue_event(EventWrapper::StaticEvent(Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::ReceivedCharacter(character),
+                            }));
+                        }
                     }
                 }
             }
@@ -873,12 +1294,18 @@ declare_class!(
                 let state_ptr: *mut c_void = *self.ivar("winitState");
                 let state = &mut *(state_ptr as *mut ViewState);
 
-                let scancode = 0x2f;
-                let virtual_keycode = scancode_to_keycode(scancode);
-                debug_assert_eq!(virtual_keycode, Some(VirtualKeyCode::Period));
-
                 let event: id = msg_send![NSApp(), currentEvent];
 
+                // The period isn't at the same position on every layout, so
+                // the scancode is taken from the key event when there's one,
+                // and from the ANSI layout otherwise.
+                let scancode = if event != nil && event.eventType() == NSEventType::NSKeyDown {
+                    get_scancode(event)
+                } else {
+                    0x2f
+                };
+                let virtual_keycode = Some(VirtualKeyCode::Period);
+
                 update_potentially_stale_modifiers(state, event);
 
                 #[allow(deprecated)]
@@ -910,6 +1337,12 @@ declare_class!(
         #[sel(mouseUp:)]
         fn mouse_up(&self, event: id) {
             trace_scope!("mouseUp:");
+            unsafe {
+                let state_ptr: *mut c_void = *self.ivar("winitState");
+                let state = &mut *(state_ptr as *mut ViewState);
+                // The mouse was released before moving past the drag threshold.
+                state.pending_drag = None;
+            }
             mouse_motion(self, event);
             mouse_click(self, event, MouseButton::Left, ElementState::Released);
         }
@@ -951,6 +1384,7 @@ declare_class!(
 
         #[sel(mouseDragged:)]
         fn mouse_dragged(&self, event: id) {
+            start_pending_drag(self, event);
             mouse_motion(self, event);
         }
 
@@ -979,6 +1413,9 @@ declare_class!(
                 };
 
                 AppState::queue_event(EventWrapper::StaticEvent(enter_event));
+
+                let view: id = self as *const _ as *mut _;
+                update_cursor_hidden(view);
             }
         }
 
@@ -997,6 +1434,9 @@ declare_class!(
                 };
 
                 AppState::queue_event(EventWrapper::StaticEvent(window_event));
+
+                // Show the cursor over other windows and the titlebar.
+                state.cursor_state.lock().unwrap().set_hidden(false);
             }
         }
 
@@ -1062,7 +1502,9 @@ declare_class!(
                     },
                 };
 
-                AppState::queue_event(EventWrapper::StaticEvent(device_event));
+                if app::device_events_allowed() {
+                    AppState::queue_event(EventWrapper::StaticEvent(device_event));
+                }
                 AppState::queue_event(EventWrapper::StaticEvent(window_event));
             }
         }
@@ -1153,6 +1595,28 @@ declare_class!(
             }
         }
 
+        // Key equivalents are offered to every view of the key window before
+        // the main menu, so returning `true` keeps them from the menu.
+        #[sel(performKeyEquivalent:)]
+        fn perform_key_equivalent(&self, event: id) -> bool {
+            trace_scope!("performKeyEquivalent:");
+            unsafe {
+                let state_ptr: *mut c_void = *self.ivar("winitState");
+                let state = &mut *(state_ptr as *mut ViewState);
+
+                let first_responder: id = msg_send![state.ns_window, firstResponder];
+                let self_ptr = self as *const _ as *mut _;
+                if first_responder == self_ptr
+                    && event.eventType() == NSEventType::NSKeyDown
+                    && resolve_key_equivalent_policy(state, event) == KeyEquivalentPolicy::Window
+                {
+                    let _: () = msg_send![self, keyDown: event];
+                    return true;
+                }
+                msg_send![super(self), performKeyEquivalent: event]
+            }
+        }
+
         // Allows us to receive Ctrl-Tab and Ctrl-Esc.
         // Note that this *doesn't* help with any missing Cmd inputs.
         // https://github.com/chromium/chromium/blob/a86a8a6bcfa438fa3ac2eba6f02b3ad1f8e0756f/ui/views/cocoa/bridged_content_view.mm#L816
@@ -1168,6 +1632,32 @@ declare_class!(
             true
         }
     }
+
+    // `NSDraggingSource`, for the sessions started with `begin_drag_session`.
+    unsafe impl WinitView {
+        #[sel(draggingSession:sourceOperationMaskForDraggingContext:)]
+        fn source_operation_mask(&self, _session: id, _context: NSInteger) -> NSUInteger {
+            trace_scope!("draggingSession:sourceOperationMaskForDraggingContext:");
+            // The items are always copied, never moved out of the application.
+            NSDragOperationCopy | NSDragOperationGeneric
+        }
+
+        #[sel(draggingSession:endedAtPoint:operation:)]
+        fn dragging_session_ended(&self, _session: id, _point: NSPoint, operation: NSUInteger) {
+            trace_scope!("draggingSession:endedAtPoint:operation:");
+            unsafe {
+                let state_ptr: *mut c_void = *self.ivar("winitState");
+                let state = &mut *(state_ptr as *mut ViewState);
+
+                AppState::queue_event(EventWrapper::StaticEvent(Event::WindowEvent {
+                    window_id: WindowId(get_window_id(state.ns_window)),
+                    event: WindowEvent::DragSessionEnded {
+                        accepted: operation != NSDragOperationNone,
+                    },
+                }));
+            }
+        }
+    }
 );
 
 impl WinitView {
@@ -1177,3 +1667,139 @@ impl WinitView {
         unsafe { id_to_string_lossy(input_source) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+
+    /// Stands in for the app-wide hide count of `NSCursor`.
+    struct HideCount(Cell<i32>);
+
+    impl HideCount {
+        fn set_hidden(&self, hidden: &mut bool, new_hidden: bool) {
+            set_cursor_hidden_with(
+                hidden,
+                new_hidden,
+                || self.0.set(self.0.get() + 1),
+                || self.0.set(self.0.get() - 1),
+            );
+        }
+    }
+
+    #[test]
+    fn repeated_cursor_toggles_stay_balanced() {
+        let count = HideCount(Cell::new(0));
+        let mut hidden = false;
+        for &new_hidden in &[true, true, false, false, true, false, true, true] {
+            count.set_hidden(&mut hidden, new_hidden);
+            assert_eq!(hidden, new_hidden);
+            assert_eq!(count.0.get(), new_hidden as i32);
+        }
+        count.set_hidden(&mut hidden, false);
+        assert_eq!(count.0.get(), 0);
+    }
+
+    #[test]
+    fn cursor_is_hidden_by_the_grab_regardless_of_visible() {
+        assert!(cursor_is_visible(true, false));
+        assert!(!cursor_is_visible(false, false));
+        assert!(!cursor_is_visible(true, true));
+        assert!(!cursor_is_visible(false, true));
+    }
+
+    #[test]
+    fn cursor_is_only_hidden_over_the_key_view() {
+        assert!(cursor_should_hide(false, true, true));
+        assert!(!cursor_should_hide(false, false, true));
+        assert!(!cursor_should_hide(false, true, false));
+        assert!(!cursor_should_hide(true, true, true));
+    }
+
+    #[test]
+    fn cursor_is_hidden_again_after_a_fullscreen_transition() {
+        let calls = RefCell::new(Vec::new());
+        let hide = || calls.borrow_mut().push("hide");
+        let unhide = || calls.borrow_mut().push("unhide");
+
+        // Locked and hidden, then the window server shows the cursor while
+        // entering and exiting fullscreen.
+        let mut hidden = false;
+        set_cursor_hidden_with(&mut hidden, true, hide, unhide);
+        for _ in 0..2 {
+            reapply_cursor_hidden_with(&mut hidden, true, hide, unhide);
+            assert!(hidden);
+        }
+        assert_eq!(
+            *calls.borrow(),
+            ["hide", "unhide", "hide", "unhide", "hide"]
+        );
+
+        // Nothing is called if the cursor isn't hidden.
+        calls.borrow_mut().clear();
+        let mut hidden = false;
+        reapply_cursor_hidden_with(&mut hidden, false, hide, unhide);
+        assert!(!hidden);
+        assert!(calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn focus_lost_during_composition_leaves_preedit() {
+        assert_eq!(
+            ime_state_after_focus_lost(&ImeState::Preedit, true),
+            ImeState::Enabled
+        );
+        assert_eq!(
+            ime_state_after_focus_lost(&ImeState::Commited, true),
+            ImeState::Enabled
+        );
+        assert_eq!(
+            ime_state_after_focus_lost(&ImeState::Enabled, true),
+            ImeState::Enabled
+        );
+        assert_eq!(
+            ime_state_after_focus_lost(&ImeState::Disabled, true),
+            ImeState::Disabled
+        );
+    }
+
+    #[test]
+    fn focus_lost_with_ime_not_allowed_disables_it() {
+        for ime_state in [
+            ImeState::Disabled,
+            ImeState::Enabled,
+            ImeState::Preedit,
+            ImeState::Commited,
+        ] {
+            assert_eq!(
+                ime_state_after_focus_lost(&ime_state, false),
+                ImeState::Disabled
+            );
+        }
+    }
+
+    #[test]
+    fn resized_reports_the_size_of_the_frame() {
+        // The content size that a maximum of 400x300 points resizes a window
+        // to, with a single `setFrame:`, and so a single `Resized`.
+        let frame_size = NSSize::new(400.0, 300.0);
+        assert_eq!(resized_size(frame_size, 1.0), PhysicalSize::new(400, 300));
+        assert_eq!(resized_size(frame_size, 1.5), PhysicalSize::new(600, 450));
+        assert_eq!(resized_size(frame_size, 2.0), PhysicalSize::new(800, 600));
+    }
+
+    #[test]
+    fn ime_position_is_from_the_top_left_corner_of_the_view() {
+        let position = LogicalPosition::new(10.0, 20.0);
+
+        // A view below a 28 points titlebar.
+        let point = ime_point_in_view(position, 600.0);
+        assert_eq!((point.x, point.y), (10.0, 580.0));
+
+        // A full-size content view, which extends under the titlebar: the
+        // position is still from the top of the view, and so of the window.
+        let point = ime_point_in_view(position, 628.0);
+        assert_eq!((point.x, point.y), (10.0, 608.0));
+    }
+}
@@ -1,7 +1,7 @@
 use std::{
     collections::VecDeque,
     convert::TryInto,
-    f64, ops,
+    f64, mem, ops,
     os::raw::c_void,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -15,36 +15,43 @@ use raw_window_handle::{
 
 use crate::{
     dpi::{
-        LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size, Size::Logical,
+        validate_scale_factor, LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize,
+        Position, Size, Size::Logical,
     },
-    error::{ExternalError, NotSupportedError, OsError as RootOsError},
+    error::{ExternalError, FullscreenError, NotSupportedError, OsError as RootOsError},
+    event::{KeyboardInput, WindowEvent},
     icon::Icon,
     monitor::{MonitorHandle as RootMonitorHandle, VideoMode as RootVideoMode},
-    platform::macos::WindowExtMacOS,
+    platform::macos::{
+        BlurBlendingMode, BlurMaterial, DragItem, KeyEquivalentPolicy, SafeAreaInsets, TabbingMode,
+        TitleLineBreakMode, WindowExtMacOS, WindowLevelPreset,
+    },
     platform_impl::platform::{
         app_state::AppState,
+        display_link::DisplayLink,
         ffi,
-        monitor::{self, MonitorHandle, VideoMode},
+        monitor::{self, MonitorHandle, NativeDisplayMode, VideoMode},
         util::{self, IdRef},
-        view::{self, new_view, ViewState},
+        view::{self, new_view, CursorState, ViewState},
         window_delegate::new_delegate,
         OsError,
     },
     window::{
-        CursorGrabMode, CursorIcon, Fullscreen, UserAttentionType, WindowAttributes,
-        WindowId as RootWindowId,
+        CursorGrabMode, CursorIcon, Fullscreen, Theme, UserAttentionType, Window as RootWindow,
+        WindowAttributes, WindowId as RootWindowId,
     },
 };
 use cocoa::{
     appkit::{
-        self, CGFloat, NSApp, NSApplication, NSApplicationPresentationOptions, NSColor,
-        NSRequestUserAttentionType, NSScreen, NSView, NSWindow, NSWindowButton, NSWindowStyleMask,
+        self, CGFloat, NSApp, NSApplication, NSApplicationPresentationOptions, NSColor, NSEvent,
+        NSEventModifierFlags, NSEventType, NSRequestUserAttentionType, NSScreen, NSView, NSWindow,
+        NSWindowButton, NSWindowCollectionBehavior, NSWindowStyleMask,
     },
     base::{id, nil},
     foundation::{NSDictionary, NSPoint, NSRect, NSSize},
 };
-use core_graphics::display::{CGDisplay, CGDisplayMode};
-use objc2::foundation::{is_main_thread, NSObject, NSUInteger};
+use core_graphics::display::{CGDirectDisplayID, CGDisplay};
+use objc2::foundation::{is_main_thread, NSInteger, NSObject, NSUInteger};
 use objc2::rc::autoreleasepool;
 use objc2::runtime::{Bool, Object};
 use objc2::{declare_class, ClassType};
@@ -89,6 +96,8 @@ pub struct PlatformSpecificWindowBuilderAttributes {
     pub resize_increments: Option<LogicalSize<f64>>,
     pub disallow_hidpi: bool,
     pub has_shadow: bool,
+    pub tabbing_mode: TabbingMode,
+    pub centered_on: Option<MonitorHandle>,
 }
 
 impl Default for PlatformSpecificWindowBuilderAttributes {
@@ -104,6 +113,8 @@ impl Default for PlatformSpecificWindowBuilderAttributes {
             resize_increments: None,
             disallow_hidpi: false,
             has_shadow: true,
+            tabbing_mode: TabbingMode::Automatic,
+            centered_on: None,
         }
     }
 }
@@ -156,7 +167,7 @@ fn create_window(
             Some(screen) => NSScreen::frame(screen),
             None => {
                 let screen = NSScreen::mainScreen(nil);
-                let scale_factor = NSScreen::backingScaleFactor(screen) as f64;
+                let scale_factor = util::scale_factor(NSScreen::backingScaleFactor(screen));
                 let (width, height) = match attrs.inner_size {
                     Some(size) => {
                         let logical = size.to_logical(scale_factor);
@@ -166,7 +177,7 @@ fn create_window(
                 };
                 let (left, bottom) = match attrs.position {
                     Some(position) => {
-                        let logical = util::window_position(position.to_logical(scale_factor));
+                        let logical = util::ns_point(position.to_logical(scale_factor));
                         // macOS wants the position of the bottom left corner,
                         // but caller is setting the position of top left corner
                         (logical.x, logical.y - height)
@@ -212,6 +223,10 @@ fn create_window(
 
         ns_window.non_nil().map(|ns_window| {
             let title = util::ns_string_id_ref(&attrs.title);
+            // The window is owned by `UnownedWindow`, which holds a reference
+            // to it until it's dropped, so it must not be released by AppKit
+            // when it's closed, whether by `close` or when dropped. The close
+            // button only emits `CloseRequested`, see `windowShouldClose:`.
             ns_window.setReleasedWhenClosed_(Bool::NO.as_raw());
             ns_window.setTitle_(*title);
             ns_window.setAcceptsMouseMovedEvents_(Bool::YES.as_raw());
@@ -223,15 +238,7 @@ fn create_window(
                 ns_window.setTitleVisibility_(appkit::NSWindowTitleVisibility::NSWindowTitleHidden);
             }
             if pl_attrs.titlebar_buttons_hidden {
-                for titlebar_button in &[
-                    NSWindowButton::NSWindowFullScreenButton,
-                    NSWindowButton::NSWindowMiniaturizeButton,
-                    NSWindowButton::NSWindowCloseButton,
-                    NSWindowButton::NSWindowZoomButton,
-                ] {
-                    let button = ns_window.standardWindowButton_(*titlebar_button);
-                    let _: () = msg_send![button, setHidden: true];
-                }
+                util::set_titlebar_buttons_hidden(*ns_window, true);
             }
             if pl_attrs.movable_by_window_background {
                 ns_window.setMovableByWindowBackground_(Bool::YES.as_raw());
@@ -241,23 +248,22 @@ fn create_window(
                 let _: () = msg_send![*ns_window, setLevel: ffi::kCGFloatingWindowLevelKey];
             }
 
-            if let Some(increments) = pl_attrs.resize_increments {
-                let (w, h) = (increments.width, increments.height);
-                if w >= 1.0 && h >= 1.0 {
-                    let size = NSSize::new(w as CGFloat, h as CGFloat);
-                    // It was concluded (#2411) that there is never a use-case for
-                    // "outer" resize increments, hence we set "inner" ones here.
-                    // ("outer" in macOS being just resizeIncrements, and "inner" - contentResizeIncrements)
-                    // This is consistent with X11 size hints behavior
-                    ns_window.setContentResizeIncrements_(size);
-                }
-            }
-
             if !pl_attrs.has_shadow {
                 ns_window.setHasShadow_(Bool::NO.as_raw());
             }
+            if pl_attrs.tabbing_mode != TabbingMode::Automatic {
+                let tabbing_mode = tabbing_mode_to_ns(pl_attrs.tabbing_mode);
+                let _: () = msg_send![*ns_window, setTabbingMode: tabbing_mode];
+            }
             if attrs.position.is_none() {
-                ns_window.center();
+                match pl_attrs
+                    .centered_on
+                    .as_ref()
+                    .and_then(|monitor| monitor.ns_screen())
+                {
+                    Some(ns_screen) => util::center_on_screen(*ns_window, ns_screen),
+                    None => ns_window.center(),
+                }
             }
             ns_window
         })
@@ -290,6 +296,10 @@ declare_class!(
 #[derive(Default)]
 pub struct SharedState {
     pub resizable: bool,
+    /// Whether the window can be moved by the user, as set by `set_movable`.
+    /// Simple fullscreen makes the window temporarily immovable, and restores
+    /// this value upon exiting it.
+    movable: bool,
     pub fullscreen: Option<Fullscreen>,
     // This is true between windowWillEnterFullScreen and windowDidEnterFullScreen
     // or windowWillExitFullScreen and windowDidExitFullScreen.
@@ -299,8 +309,18 @@ pub struct SharedState {
     // Set target_fullscreen and do after fullscreen transition is end.
     pub target_fullscreen: Option<Option<Fullscreen>>,
     pub maximized: bool,
+    /// Whether the application requested `CursorGrabMode::Locked`. The grab
+    /// is suspended while the window isn't the key window, and restored when
+    /// it becomes key again.
+    cursor_locked: bool,
+    /// Whether the cursor stays visible while it's locked, see
+    /// `WindowExtMacOS::set_cursor_visible_while_locked`.
+    cursor_visible_while_locked: bool,
     pub standard_frame: Option<NSRect>,
     is_simple_fullscreen: bool,
+    /// The level of the window before entering simple fullscreen above the
+    /// menu bar, see `set_borderless_fullscreen_on_current_space`.
+    simple_fullscreen_level: Option<NSInteger>,
     pub saved_style: Option<NSWindowStyleMask>,
     /// Presentation options saved before entering `set_simple_fullscreen`, and
     /// restored upon exiting it. Also used when transitioning from Borderless to
@@ -308,7 +328,36 @@ pub struct SharedState {
     /// bar in exclusive fullscreen but want to restore the original options when
     /// transitioning back to borderless fullscreen.
     save_presentation_opts: Option<NSApplicationPresentationOptions>,
-    pub saved_desktop_display_mode: Option<(CGDisplay, CGDisplayMode)>,
+    /// The display mode that was in use before exclusive fullscreen changed
+    /// it, and the display it's for.
+    saved_desktop_display_mode: Option<(CGDirectDisplayID, NativeDisplayMode)>,
+    /// Present while redraws are paced to the display, see `set_display_link_paced`.
+    display_link: Option<DisplayLink>,
+    /// Whether the window is fully hidden, as last reported by
+    /// `windowDidChangeOcclusionState:`.
+    occluded: bool,
+    /// Whether `request_redraw` delivers redraws while the window is occluded,
+    /// as set by `set_redraw_while_occluded`.
+    redraw_while_occluded: bool,
+    /// Whether a redraw was requested while the window was occluded, to be
+    /// delivered once it's revealed.
+    redraw_deferred: bool,
+    /// Kept so that either can be changed without the other, see `set_blur_material`.
+    blur_material: Option<BlurMaterial>,
+    blur_blending_mode: BlurBlendingMode,
+    /// The scale of the view's layer, as set by `set_surface_scale`. Reapplied
+    /// when the backing scale factor changes, which resets it.
+    surface_scale: Option<f64>,
+    /// The radius set by `set_corner_radius`, as it's only applied on the main thread.
+    corner_radius: Option<f64>,
+    /// The maximum inner size set by the application, which is further limited
+    /// to the work area of the monitor while `constrained_to_monitor` is set.
+    max_inner_size: Option<LogicalSize<f64>>,
+    constrained_to_monitor: bool,
+    /// The resize increments set by the application, kept apart from the
+    /// window's, which are 1x1 when there are none, so that 1x1 increments
+    /// are still returned by `resize_increments`.
+    resize_increments: Option<LogicalSize<f64>>,
 }
 
 impl SharedState {
@@ -316,12 +365,47 @@ impl SharedState {
         self.standard_frame
             .unwrap_or_else(|| NSRect::new(NSPoint::new(50.0, 50.0), NSSize::new(800.0, 600.0)))
     }
+
+    fn resize_increments(&self, scale_factor: f64) -> Option<PhysicalSize<u32>> {
+        self.resize_increments
+            .map(|increments| increments.to_physical(scale_factor))
+    }
+
+    /// Stores the resize increments set by the application, and returns the
+    /// content resize increments of the window, which are 1x1 when there are
+    /// none.
+    fn set_resize_increments(&mut self, increments: Option<Size>, scale_factor: f64) -> NSSize {
+        self.resize_increments =
+            increments.map(|increments| resize_increments_to_logical(increments, scale_factor));
+        self.resize_increments
+            .map(|increments| {
+                NSSize::new(increments.width as CGFloat, increments.height as CGFloat)
+            })
+            .unwrap_or_else(|| NSSize::new(1.0, 1.0))
+    }
+
+    /// Records the maximized state, saving `frame` as the standard frame if the
+    /// window isn't zoomed. Returns whether the window is to be zoomed or
+    /// restored now, which is deferred until fullscreen is exited otherwise.
+    pub(crate) fn update_maximized(
+        &mut self,
+        maximized: bool,
+        is_zoomed: bool,
+        frame: NSRect,
+    ) -> bool {
+        if !is_zoomed {
+            self.standard_frame = Some(frame);
+        }
+        self.maximized = maximized;
+        self.fullscreen.is_none()
+    }
 }
 
 impl From<WindowAttributes> for SharedState {
     fn from(attribs: WindowAttributes) -> Self {
         SharedState {
             resizable: attribs.resizable,
+            movable: true,
             // This fullscreen field tracks the current state of the window
             // (as seen by `WindowDelegate`), and since the window hasn't
             // actually been fullscreened yet, we can't set it yet. This is
@@ -373,11 +457,48 @@ impl Drop for SharedStateMutexGuard<'_> {
     }
 }
 
+/// The sizes and scale factor of a window, cached so that they can be read
+/// without messaging AppKit, see `UnownedWindow::update_geometry`.
+///
+/// This is kept out of `SharedState`, as it's updated from notifications that
+/// AppKit may post while the shared state is locked, e.g. when resizing.
+#[derive(Debug, Clone, Copy)]
+struct Geometry {
+    inner_size: LogicalSize<f64>,
+    outer_size: LogicalSize<f64>,
+    scale_factor: f64,
+}
+
+impl Geometry {
+    // Must be called on the main thread.
+    unsafe fn read(ns_window: id, ns_view: id) -> Self {
+        // The view is the content view, so its frame is the content rect of
+        // the window, without the title bar.
+        Geometry::new(
+            NSView::frame(ns_view).size,
+            NSWindow::frame(ns_window).size,
+            util::scale_factor(NSWindow::backingScaleFactor(ns_window)),
+        )
+    }
+
+    fn new(content_size: NSSize, frame_size: NSSize, scale_factor: f64) -> Self {
+        Geometry {
+            inner_size: LogicalSize::new(content_size.width as f64, content_size.height as f64),
+            outer_size: LogicalSize::new(frame_size.width as f64, frame_size.height as f64),
+            scale_factor,
+        }
+    }
+}
+
 pub struct UnownedWindow {
     pub ns_window: IdRef, // never changes
     pub ns_view: IdRef,   // never changes
     shared_state: Arc<Mutex<SharedState>>,
+    geometry: Mutex<Geometry>,
     decorations: AtomicBool,
+    /// Whether the window was closed with `WindowExtMacOS::close`, after which
+    /// it's never shown again.
+    closed: AtomicBool,
     pub inner_rect: Option<PhysicalSize<u32>>,
 }
 
@@ -406,7 +527,14 @@ impl UnownedWindow {
             ns_window.setInitialFirstResponder_(*ns_view);
         }
 
-        let scale_factor = unsafe { NSWindow::backingScaleFactor(*ns_window) as f64 };
+        let scale_factor = util::scale_factor(unsafe { NSWindow::backingScaleFactor(*ns_window) });
+        let max_inner_size = win_attribs
+            .max_inner_size
+            .map(|dim| size_constraint_to_logical(dim, scale_factor, Bound::Max));
+        // The cross-platform attribute takes precedence over the platform-specific one.
+        let resize_increments = win_attribs
+            .resize_increments
+            .or_else(|| pl_attribs.resize_increments.map(Size::Logical));
 
         unsafe {
             if win_attribs.transparent {
@@ -415,12 +543,11 @@ impl UnownedWindow {
             }
 
             if let Some(dim) = win_attribs.min_inner_size {
-                let logical_dim = dim.to_logical(scale_factor);
+                let logical_dim = size_constraint_to_logical(dim, scale_factor, Bound::Min);
                 set_min_inner_size(*ns_window, logical_dim);
             }
-            if let Some(dim) = win_attribs.max_inner_size {
-                let logical_dim = dim.to_logical(scale_factor);
-                set_max_inner_size(*ns_window, logical_dim);
+            if let Some(max_inner_size) = max_inner_size {
+                set_max_inner_size(*ns_window, max_inner_size);
             }
 
             use cocoa::foundation::NSArray;
@@ -445,30 +572,52 @@ impl UnownedWindow {
             .inner_size
             .map(|size| size.to_physical(scale_factor));
 
+        let geometry = unsafe { Geometry::read(*ns_window, *ns_view) };
         let window = Arc::new(UnownedWindow {
             ns_view,
             ns_window,
             shared_state: Arc::new(Mutex::new(win_attribs.into())),
+            geometry: Mutex::new(geometry),
             decorations: AtomicBool::new(decorations),
+            closed: AtomicBool::new(false),
             inner_rect,
         });
+        let mut shared_state = window.lock_shared_state("new");
+        shared_state.max_inner_size = max_inner_size;
+        let increments = shared_state.set_resize_increments(resize_increments, scale_factor);
+        drop(shared_state);
+        // It was concluded (#2411) that there is never a use-case for
+        // "outer" resize increments, hence we set "inner" ones here.
+        // ("outer" in macOS being just resizeIncrements, and "inner" - contentResizeIncrements)
+        // This is consistent with X11 size hints behavior
+        unsafe { window.ns_window.setContentResizeIncrements_(increments) };
 
         let delegate = new_delegate(&window, fullscreen.is_some());
 
         // Set fullscreen mode after we setup everything
         window.set_fullscreen(fullscreen);
 
+        // Maximize synchronously before the window is shown, so that
+        // `inner_size` already returns the maximized size, and the first frame
+        // isn't drawn at the initial size.
+        if maximized {
+            unsafe {
+                util::set_maximized_sync(
+                    *window.ns_window,
+                    window.is_zoomed(),
+                    maximized,
+                    Arc::downgrade(&window.shared_state),
+                )
+            };
+        }
+
         // Setting the window as key has to happen *after* we set the fullscreen
-        // state, since otherwise we'll briefly see the window at normal size
-        // before it transitions.
+        // and maximized states, since otherwise we'll briefly see the window at
+        // normal size before it transitions.
         if visible {
             // Tightly linked with `app_state::window_activation_hack`
             unsafe { window.ns_window.makeKeyAndOrderFront_(nil) };
         }
-
-        if maximized {
-            window.set_maximized(maximized);
-        }
         trace!("Done unowned window::new");
         Ok((window, delegate))
     }
@@ -481,6 +630,16 @@ impl UnownedWindow {
         SharedStateMutexGuard::new(self.shared_state.lock().unwrap(), called_from_fn)
     }
 
+    /// Refreshes the cached geometry returned by `inner_size`, `outer_size` and
+    /// `scale_factor`, after the window or its view was resized, or its backing
+    /// scale factor changed.
+    ///
+    /// Must be called on the main thread.
+    pub(crate) fn update_geometry(&self) {
+        let geometry = unsafe { Geometry::read(*self.ns_window, *self.ns_view) };
+        *self.geometry.lock().unwrap() = geometry;
+    }
+
     fn set_style_mask_async(&self, mask: NSWindowStyleMask) {
         unsafe { util::set_style_mask_async(*self.ns_window, *self.ns_view, mask) };
     }
@@ -500,6 +659,10 @@ impl UnownedWindow {
     }
 
     pub fn set_visible(&self, visible: bool) {
+        if visible && self.closed.load(Ordering::Acquire) {
+            warn!("`set_visible(true)` was called on a closed window");
+            return;
+        }
         match visible {
             true => unsafe { util::make_key_and_order_front_async(*self.ns_window) },
             false => unsafe { util::order_out_async(*self.ns_window) },
@@ -513,55 +676,109 @@ impl UnownedWindow {
     }
 
     pub fn request_redraw(&self) {
-        AppState::queue_redraw(RootWindowId(self.id()));
+        let mut shared_state_lock = self.lock_shared_state("request_redraw");
+        if shared_state_lock.occluded && !shared_state_lock.redraw_while_occluded {
+            shared_state_lock.redraw_deferred = true;
+            return;
+        }
+        match shared_state_lock.display_link {
+            Some(ref display_link) => display_link.request_redraw(),
+            None => AppState::queue_redraw(RootWindowId(self.id())),
+        }
+    }
+
+    /// Delivers the redraw that was deferred while the window was occluded, if
+    /// any, once it's revealed.
+    ///
+    /// Called from `windowDidChangeOcclusionState:`.
+    pub(crate) fn set_occluded(&self, occluded: bool) {
+        let mut shared_state_lock = self.lock_shared_state("set_occluded");
+        shared_state_lock.occluded = occluded;
+        let redraw_deferred = !occluded && mem::take(&mut shared_state_lock.redraw_deferred);
+        drop(shared_state_lock);
+        if redraw_deferred {
+            self.request_redraw();
+        }
+    }
+
+    /// Reapplies the scale set by `set_surface_scale`, if any, after the backing
+    /// scale factor changed.
+    pub(crate) fn restore_surface_scale(&self) {
+        let surface_scale = self
+            .lock_shared_state("restore_surface_scale")
+            .surface_scale;
+        if let Some(scale) = surface_scale {
+            unsafe { util::set_contents_scale(*self.ns_view, scale) };
+        }
+    }
+
+    /// Moves the display link, if any, to the display that the window is now on.
+    pub(crate) fn update_display_link_display(&self) {
+        let shared_state_lock = self.lock_shared_state("update_display_link_display");
+        if let Some(ref display_link) = shared_state_lock.display_link {
+            display_link.set_display(self.current_monitor_inner().inner.native_identifier());
+        }
     }
 
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
         let frame_rect = unsafe { NSWindow::frame(*self.ns_window) };
-        let position = LogicalPosition::new(
-            frame_rect.origin.x as f64,
-            util::bottom_left_to_top_left(frame_rect),
-        );
         let scale_factor = self.scale_factor();
-        Ok(position.to_physical(scale_factor))
+        Ok(util::top_left(frame_rect).to_physical(scale_factor))
     }
 
     pub fn inner_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
+        let (frame_rect, content_rect) = unsafe {
+            let frame_rect = NSWindow::frame(*self.ns_window);
+            let content_rect = NSWindow::contentRectForFrameRect_(*self.ns_window, frame_rect);
+            (frame_rect, content_rect)
+        };
+        Ok(inner_position_from_outer(
+            util::top_left(frame_rect),
+            util::top_left(content_rect),
+            self.scale_factor(),
+        ))
+    }
+
+    // Unlike `inner_position`, this isn't rounded to physical pixels.
+    fn inner_position_logical(&self) -> LogicalPosition<f64> {
         let content_rect = unsafe {
             NSWindow::contentRectForFrameRect_(*self.ns_window, NSWindow::frame(*self.ns_window))
         };
-        let position = LogicalPosition::new(
-            content_rect.origin.x as f64,
-            util::bottom_left_to_top_left(content_rect),
-        );
-        let scale_factor = self.scale_factor();
-        Ok(position.to_physical(scale_factor))
+        util::top_left(content_rect)
+    }
+
+    pub fn center_on_monitor(&self, monitor: &RootMonitorHandle) {
+        unsafe { util::center_on_monitor_async(*self.ns_window, monitor.inner.clone()) }
     }
 
     pub fn set_outer_position(&self, position: Position) {
         let scale_factor = self.scale_factor();
         let position = position.to_logical(scale_factor);
         unsafe {
-            util::set_frame_top_left_point_async(*self.ns_window, util::window_position(position));
+            util::set_frame_top_left_point_async(*self.ns_window, util::ns_point(position));
         }
     }
 
     #[inline]
     pub fn inner_size(&self) -> PhysicalSize<u32> {
-        let view_frame = unsafe { NSView::frame(*self.ns_view) };
-        let logical: LogicalSize<f64> =
-            (view_frame.size.width as f64, view_frame.size.height as f64).into();
-        let scale_factor = self.scale_factor();
-        logical.to_physical(scale_factor)
+        self.inner_size_f64().cast()
+    }
+
+    #[inline]
+    pub fn inner_size_f64(&self) -> PhysicalSize<f64> {
+        let geometry = *self.geometry.lock().unwrap();
+        geometry.inner_size.to_physical(geometry.scale_factor)
     }
 
     #[inline]
     pub fn outer_size(&self) -> PhysicalSize<u32> {
-        let view_frame = unsafe { NSWindow::frame(*self.ns_window) };
-        let logical: LogicalSize<f64> =
-            (view_frame.size.width as f64, view_frame.size.height as f64).into();
-        let scale_factor = self.scale_factor();
-        logical.to_physical(scale_factor)
+        self.outer_size_f64().cast()
+    }
+
+    #[inline]
+    pub fn outer_size_f64(&self) -> PhysicalSize<f64> {
+        let geometry = *self.geometry.lock().unwrap();
+        geometry.outer_size.to_physical(geometry.scale_factor)
     }
 
     #[inline]
@@ -579,38 +796,68 @@ impl UnownedWindow {
                 height: 0.0,
             }));
             let scale_factor = self.scale_factor();
-            set_min_inner_size(*self.ns_window, dimensions.to_logical(scale_factor));
+            let dimensions = size_constraint_to_logical(dimensions, scale_factor, Bound::Min);
+            util::set_min_inner_size_sync(*self.ns_window, dimensions);
         }
     }
 
     pub fn set_max_inner_size(&self, dimensions: Option<Size>) {
+        let scale_factor = self.scale_factor();
+        self.lock_shared_state("set_max_inner_size").max_inner_size = dimensions
+            .map(|dimensions| size_constraint_to_logical(dimensions, scale_factor, Bound::Max));
+        self.apply_max_inner_size();
+    }
+
+    /// Applies the maximum inner size set by the application, limited to the
+    /// work area of the window's monitor if `constrained_to_monitor` is set.
+    fn apply_max_inner_size(&self) {
+        let (max_inner_size, constrained_to_monitor) = {
+            let shared_state_lock = self.lock_shared_state("apply_max_inner_size");
+            (
+                shared_state_lock.max_inner_size,
+                shared_state_lock.constrained_to_monitor,
+            )
+        };
+        let mut dimensions = max_inner_size.unwrap_or(LogicalSize {
+            width: std::f32::MAX as f64,
+            height: std::f32::MAX as f64,
+        });
         unsafe {
-            let dimensions = dimensions.unwrap_or(Logical(LogicalSize {
-                width: std::f32::MAX as f64,
-                height: std::f32::MAX as f64,
-            }));
-            let scale_factor = self.scale_factor();
-            set_max_inner_size(*self.ns_window, dimensions.to_logical(scale_factor));
+            let screen = self.ns_window.screen();
+            if constrained_to_monitor && screen != nil {
+                let work_area = NSWindow::contentRectForFrameRect_(
+                    *self.ns_window,
+                    NSScreen::visibleFrame(screen),
+                );
+                dimensions.width = dimensions.width.min(work_area.size.width);
+                dimensions.height = dimensions.height.min(work_area.size.height);
+            }
+            util::set_max_inner_size_sync(*self.ns_window, dimensions);
         }
     }
 
-    pub fn resize_increments(&self) -> Option<PhysicalSize<u32>> {
-        let increments = unsafe { self.ns_window.contentResizeIncrements() };
-        let (x, y) = (increments.width, increments.height);
-        if x > 1.0 || y > 1.0 {
-            Some(LogicalSize::new(x, y).to_physical(self.scale_factor()))
-        } else {
-            None
+    /// Limits the maximum inner size to the work area of the monitor that the
+    /// window is now on, if `constrained_to_monitor` is set.
+    pub(crate) fn update_monitor_constraint(&self) {
+        if self
+            .lock_shared_state("update_monitor_constraint")
+            .constrained_to_monitor
+        {
+            self.apply_max_inner_size();
         }
     }
 
+    pub fn resize_increments(&self) -> Option<PhysicalSize<u32>> {
+        let scale_factor = self.scale_factor();
+        self.lock_shared_state("resize_increments")
+            .resize_increments(scale_factor)
+    }
+
     pub fn set_resize_increments(&self, increments: Option<Size>) {
-        let size = increments
-            .map(|increments| {
-                let logical = increments.to_logical::<f64>(self.scale_factor());
-                NSSize::new(logical.width.max(1.0), logical.height.max(1.0))
-            })
-            .unwrap_or_else(|| NSSize::new(1.0, 1.0));
+        let scale_factor = self.scale_factor();
+        let size = self
+            .lock_shared_state("set_resize_increments")
+            .set_resize_increments(increments, scale_factor);
         unsafe {
             self.ns_window.setContentResizeIncrements_(size);
         }
@@ -657,7 +904,7 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<CursorGrabMode, ExternalError> {
         let associate_mouse_cursor = match mode {
             CursorGrabMode::Locked => false,
             CursorGrabMode::None => true,
@@ -666,44 +913,112 @@ impl UnownedWindow {
             }
         };
 
-        // TODO: Do this for real https://stackoverflow.com/a/40922095/5435443
-        CGDisplay::associate_mouse_and_mouse_cursor_position(associate_mouse_cursor)
-            .map_err(|status| ExternalError::Os(os_error!(OsError::CGError(status))))
+        // The grab is applied once the window becomes key, see `set_cursor_grab_suspended`.
+        let is_key_window: bool = unsafe { msg_send![*self.ns_window, isKeyWindow] };
+        if is_key_window || associate_mouse_cursor {
+            // TODO: Do this for real https://stackoverflow.com/a/40922095/5435443
+            CGDisplay::associate_mouse_and_mouse_cursor_position(associate_mouse_cursor)
+                .map_err(|status| ExternalError::Os(os_error!(OsError::CGError(status))))?;
+        }
+
+        // Only update the state once the grab was applied, so that a failure
+        // leaves both the grab and the cursor visibility unchanged.
+        let mut shared_state_lock = self.lock_shared_state("set_cursor_grab");
+        let previous_mode = if shared_state_lock.cursor_locked {
+            CursorGrabMode::Locked
+        } else {
+            CursorGrabMode::None
+        };
+        shared_state_lock.cursor_locked = !associate_mouse_cursor;
+        let hidden_by_grab = cursor_hidden_by_grab(
+            shared_state_lock.cursor_locked,
+            shared_state_lock.cursor_visible_while_locked,
+        );
+        drop(shared_state_lock);
+        self.update_cursor_state(|cursor_state| cursor_state.hidden_by_grab = hidden_by_grab);
+
+        Ok(previous_mode)
+    }
+
+    /// Temporarily releases a locked cursor while the window isn't the key
+    /// window, and locks it again once the window becomes key.
+    ///
+    /// Called from `windowDidResignKey:` and `windowDidBecomeKey:`.
+    pub(crate) fn set_cursor_grab_suspended(&self, suspended: bool) {
+        let shared_state_lock = self.lock_shared_state("set_cursor_grab_suspended");
+        if !shared_state_lock.cursor_locked {
+            return;
+        }
+        if let Err(status) = CGDisplay::associate_mouse_and_mouse_cursor_position(suspended) {
+            warn!(
+                "Failed to update cursor grab on focus change: CGError {}",
+                status
+            );
+        }
+    }
+
+    /// Applies the cursor grab and visibility again once a fullscreen
+    /// transition is over, as the window server releases the grab and shows
+    /// the cursor while it animates the window.
+    ///
+    /// Called from `windowDidEnterFullScreen:` and `windowDidExitFullScreen:`.
+    pub(crate) fn reapply_cursor_state(&self) {
+        let is_key_window: bool = unsafe { msg_send![*self.ns_window, isKeyWindow] };
+        let cursor_locked = self.lock_shared_state("reapply_cursor_state").cursor_locked;
+        if should_relock_cursor(cursor_locked, is_key_window) {
+            self.set_cursor_grab_suspended(false);
+        }
+        unsafe {
+            let _: () = msg_send![*self.ns_window,
+                invalidateCursorRectsForView:*self.ns_view
+            ];
+            view::reapply_cursor_hidden(*self.ns_view);
+        }
     }
 
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
+        self.update_cursor_state(|cursor_state| cursor_state.visible = visible);
+    }
+
+    /// Applies `f` to the cursor state of the view, and updates the cursor if
+    /// its visibility changed.
+    fn update_cursor_state(&self, f: impl FnOnce(&mut CursorState)) {
         let view_state: &ViewState = unsafe {
             let ns_view: &Object = (*self.ns_view).as_ref().expect("failed to deref");
             let state_ptr: *const c_void = *ns_view.ivar("winitState");
             &*(state_ptr as *const ViewState)
         };
         let mut cursor_state = view_state.cursor_state.lock().unwrap();
-        if visible != cursor_state.visible {
-            cursor_state.visible = visible;
+        let was_visible = cursor_state.is_visible();
+        f(&mut cursor_state);
+        if was_visible != cursor_state.is_visible() {
             drop(cursor_state);
             unsafe {
                 let _: () = msg_send![*self.ns_window,
                     invalidateCursorRectsForView:*self.ns_view
                 ];
+                util::update_cursor_hidden_async(*self.ns_view);
             }
         }
     }
 
     #[inline]
     pub fn scale_factor(&self) -> f64 {
-        unsafe { NSWindow::backingScaleFactor(*self.ns_window) as _ }
+        self.geometry.lock().unwrap().scale_factor
     }
 
     #[inline]
     pub fn set_cursor_position(&self, cursor_position: Position) -> Result<(), ExternalError> {
-        let physical_window_position = self.inner_position().unwrap();
+        // Both the inner position and `CGWarpMouseCursorPosition` are in winit
+        // screen coordinates. The inner position isn't rounded to physical
+        // pixels, which would offset the cursor by up to a pixel.
+        let window_position = self.inner_position_logical();
         let scale_factor = self.scale_factor();
-        let window_position = physical_window_position.to_logical::<CGFloat>(scale_factor);
-        let logical_cursor_position = cursor_position.to_logical::<CGFloat>(scale_factor);
+        let logical_cursor_position = cursor_position.to_logical::<f64>(scale_factor);
         let point = appkit::CGPoint {
-            x: logical_cursor_position.x + window_position.x,
-            y: logical_cursor_position.y + window_position.y,
+            x: (logical_cursor_position.x + window_position.x) as CGFloat,
+            y: (logical_cursor_position.y + window_position.y) as CGFloat,
         };
         CGDisplay::warp_mouse_cursor_position(point)
             .map_err(|e| ExternalError::Os(os_error!(OsError::CGError(e))))?;
@@ -716,8 +1031,15 @@ impl UnownedWindow {
     #[inline]
     pub fn drag_window(&self) -> Result<(), ExternalError> {
         unsafe {
-            let event: id = msg_send![NSApp(), currentEvent];
-            let _: () = msg_send![*self.ns_window, performWindowDragWithEvent: event];
+            let event = drag_event(*self.ns_window);
+            if event == nil {
+                return Err(ExternalError::Os(os_error!(OsError::CreationError(
+                    "Couldn't create an `NSEvent` to drag the window with"
+                ))));
+            }
+            if !view::defer_drag(*self.ns_view, event) {
+                let _: () = msg_send![*self.ns_window, performWindowDragWithEvent: event];
+            }
         }
 
         Ok(())
@@ -732,6 +1054,23 @@ impl UnownedWindow {
         Ok(())
     }
 
+    #[inline]
+    pub fn set_content_protected(&self, protected: bool) {
+        let sharing_type = if protected {
+            ffi::NSWindowSharingNone
+        } else {
+            ffi::NSWindowSharingReadOnly
+        };
+        unsafe { msg_send![*self.ns_window, setSharingType: sharing_type] }
+    }
+
+    #[inline]
+    pub fn is_content_protected(&self) -> bool {
+        let sharing_type: ffi::NSWindowSharingType =
+            unsafe { msg_send![*self.ns_window, sharingType] };
+        sharing_type == ffi::NSWindowSharingNone
+    }
+
     pub(crate) fn is_zoomed(&self) -> bool {
         // because `isZoomed` doesn't work if the window's borderless,
         // we make it resizable temporalily.
@@ -754,6 +1093,109 @@ impl UnownedWindow {
         is_zoomed
     }
 
+    /// Enters or exits simple fullscreen. When `elevated`, the window covers the
+    /// menu bar and the Dock by being raised above them, instead of hiding them
+    /// with the presentation options of the application.
+    ///
+    /// Exiting restores whichever of the two was changed upon entering, so
+    /// either mode may be exited with either `WindowExtMacOS` setter.
+    fn set_simple_fullscreen_mode(&self, fullscreen: bool, elevated: bool) -> bool {
+        let mut shared_state_lock = self.shared_state.lock().unwrap();
+
+        unsafe {
+            let app = NSApp();
+            let is_native_fullscreen = shared_state_lock.fullscreen.is_some();
+            let is_simple_fullscreen = shared_state_lock.is_simple_fullscreen;
+
+            // Do nothing if native fullscreen is active.
+            if is_native_fullscreen
+                || (fullscreen && is_simple_fullscreen)
+                || (!fullscreen && !is_simple_fullscreen)
+            {
+                return false;
+            }
+
+            if fullscreen {
+                // Remember the original window's settings
+                // Exclude title bar
+                shared_state_lock.standard_frame = Some(NSWindow::contentRectForFrameRect_(
+                    *self.ns_window,
+                    NSWindow::frame(*self.ns_window),
+                ));
+                shared_state_lock.saved_style = Some(self.ns_window.styleMask());
+                shared_state_lock.save_presentation_opts = Some(app.presentationOptions_());
+
+                // Tell our window's state that we're in fullscreen
+                shared_state_lock.is_simple_fullscreen = true;
+
+                if elevated {
+                    // Stay above the menu bar and the Dock, but below the
+                    // windows of other applications that are meant to appear
+                    // over everything, such as notifications.
+                    shared_state_lock.simple_fullscreen_level = Some(self.ns_window.level());
+                    let level = ffi::CGWindowLevelForKey(ffi::kCGMainMenuWindowLevelKey as _) + 1;
+                    self.ns_window.setLevel_(level as NSInteger);
+                } else {
+                    // Simulate pre-Lion fullscreen by hiding the dock and menu bar
+                    let presentation_options =
+                        NSApplicationPresentationOptions::NSApplicationPresentationAutoHideDock |
+                        NSApplicationPresentationOptions::NSApplicationPresentationAutoHideMenuBar;
+                    app.setPresentationOptions_(presentation_options);
+                }
+
+                // Hide the titlebar
+                util::toggle_style_mask(
+                    *self.ns_window,
+                    *self.ns_view,
+                    NSWindowStyleMask::NSTitledWindowMask,
+                    false,
+                );
+
+                // Set the window frame to the screen frame size
+                let screen = self.ns_window.screen();
+                let screen_frame = NSScreen::frame(screen);
+                NSWindow::setFrame_display_(*self.ns_window, screen_frame, Bool::YES.as_raw());
+
+                // Fullscreen windows can't be resized, minimized, or moved
+                util::toggle_style_mask(
+                    *self.ns_window,
+                    *self.ns_view,
+                    NSWindowStyleMask::NSMiniaturizableWindowMask,
+                    false,
+                );
+                util::toggle_style_mask(
+                    *self.ns_window,
+                    *self.ns_view,
+                    NSWindowStyleMask::NSResizableWindowMask,
+                    false,
+                );
+                NSWindow::setMovable_(*self.ns_window, Bool::NO.as_raw());
+
+                true
+            } else {
+                let new_mask = self.saved_style(&mut *shared_state_lock);
+                self.set_style_mask_async(new_mask);
+                shared_state_lock.is_simple_fullscreen = false;
+
+                if let Some(presentation_opts) = shared_state_lock.save_presentation_opts {
+                    app.setPresentationOptions_(presentation_opts);
+                }
+                if let Some(level) = shared_state_lock.simple_fullscreen_level.take() {
+                    self.ns_window.setLevel_(level);
+                }
+
+                let frame = shared_state_lock.saved_standard_frame();
+                NSWindow::setFrame_display_(*self.ns_window, frame, Bool::YES.as_raw());
+                NSWindow::setMovable_(
+                    *self.ns_window,
+                    Bool::new(shared_state_lock.movable).as_raw(),
+                );
+
+                true
+            }
+        }
+    }
+
     fn saved_style(&self, shared_state: &mut SharedState) -> NSWindowStyleMask {
         let base_mask = shared_state
             .saved_style
@@ -776,11 +1218,22 @@ impl UnownedWindow {
 
         let maximized = shared_state_lock.maximized;
         let mask = self.saved_style(&mut *shared_state_lock);
+        let frame = shared_state_lock.saved_standard_frame();
 
         drop(shared_state_lock);
 
-        self.set_style_mask_async(mask);
-        self.set_maximized(maximized);
+        for step in fullscreen_exit_steps(maximized) {
+            match step {
+                FullscreenExitStep::StyleMask => self.set_style_mask_async(mask),
+                FullscreenExitStep::Frame => unsafe {
+                    util::set_frame_async(*self.ns_window, frame)
+                },
+                FullscreenExitStep::Maximized => self.set_maximized(maximized),
+                FullscreenExitStep::SizeConstraints => unsafe {
+                    util::apply_inner_size_constraints_async(*self.ns_window)
+                },
+            }
+        }
     }
 
     #[inline]
@@ -791,9 +1244,23 @@ impl UnownedWindow {
         }
 
         if minimized {
+            // `miniaturize:` does nothing if the window can't be minimized by
+            // the user, e.g. if its decorations were removed, so we make it
+            // minimizable temporarily.
+            let curr_mask = unsafe { self.ns_window.styleMask() };
+            let temp_mask = minimizable_mask(curr_mask);
+            if let Some(temp_mask) = temp_mask {
+                self.set_style_mask_sync(temp_mask);
+            }
+
             unsafe {
                 NSWindow::miniaturize_(*self.ns_window, *self.ns_window);
             }
+
+            // Roll back temp styles
+            if temp_mask.is_some() {
+                self.set_style_mask_async(curr_mask);
+            }
         } else {
             unsafe {
                 NSWindow::deminiaturize_(*self.ns_window, *self.ns_window);
@@ -919,16 +1386,34 @@ impl UnownedWindow {
                     );
                 }
 
-                assert_eq!(ffi::CGDisplayCapture(display_id), ffi::kCGErrorSuccess);
-            }
+                // The mode is saved before it's first changed, as the one that
+                // `CGRestorePermanentDisplayConfiguration` restores may differ
+                // from the one the user chose, e.g. by not being scaled.
+                let mut saved_mode = false;
+                {
+                    let mut shared_state_lock = self.lock_shared_state("set_fullscreen");
+                    if shared_state_lock.saved_desktop_display_mode.is_none() {
+                        let mode = ffi::CGDisplayCopyDisplayMode(display_id);
+                        if !mode.is_null() {
+                            shared_state_lock.saved_desktop_display_mode =
+                                Some((display_id, NativeDisplayMode(mode)));
+                            saved_mode = true;
+                        }
+                    }
+                }
 
-            unsafe {
-                let result = ffi::CGDisplaySetDisplayMode(
+                let mut result = Ok(());
+                if ffi::CGDisplayCapture(display_id) != ffi::kCGErrorSuccess {
+                    result = Err(FullscreenError::DisplayUnavailable);
+                } else if ffi::CGDisplaySetDisplayMode(
                     display_id,
                     video_mode.video_mode.native_mode.0,
                     std::ptr::null(),
-                );
-                assert!(result == ffi::kCGErrorSuccess, "failed to set video mode");
+                ) != ffi::kCGErrorSuccess
+                {
+                    ffi::CGDisplayRelease(display_id);
+                    result = Err(FullscreenError::VideoModeUnavailable);
+                }
 
                 // After the display has been configured, fade back in
                 // asynchronously
@@ -945,6 +1430,20 @@ impl UnownedWindow {
                     );
                     ffi::CGReleaseDisplayFadeReservation(fade_token);
                 }
+
+                // Leave the window as it was, and let the application know.
+                if let Err(error) = result {
+                    if saved_mode {
+                        self.lock_shared_state("set_fullscreen")
+                            .saved_desktop_display_mode = None;
+                    }
+                    warn!("Failed to enter exclusive fullscreen: {}", error);
+                    util::queue_window_event_async(
+                        *self.ns_window,
+                        WindowEvent::FullscreenFailed(error),
+                    );
+                    return;
+                }
             }
         }
 
@@ -970,7 +1469,10 @@ impl UnownedWindow {
                 );
             },
             (&Some(Fullscreen::Exclusive(RootVideoMode { ref video_mode })), &None) => unsafe {
-                util::restore_display_mode_async(video_mode.monitor().inner.native_identifier());
+                util::restore_display_mode_async(
+                    video_mode.monitor().inner.native_identifier(),
+                    shared_state_lock.saved_desktop_display_mode.take(),
+                );
                 // Rest of the state is restored by `window_did_exit_fullscreen`
                 util::toggle_full_screen_async(
                     *self.ns_window,
@@ -1011,7 +1513,10 @@ impl UnownedWindow {
                     });
                 NSApp().setPresentationOptions_(presentation_options);
 
-                util::restore_display_mode_async(video_mode.monitor().inner.native_identifier());
+                util::restore_display_mode_async(
+                    video_mode.monitor().inner.native_identifier(),
+                    shared_state_lock.saved_desktop_display_mode.take(),
+                );
 
                 // Restore the normal window level following the Borderless fullscreen
                 // `CGShieldingWindowLevel() + 1` hack.
@@ -1101,7 +1606,10 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn focus_window(&self) {
+    pub fn focus_window(&self) -> bool {
+        if self.closed.load(Ordering::Acquire) {
+            return false;
+        }
         let is_minimized: bool = unsafe { msg_send![*self.ns_window, isMiniaturized] };
         let is_visible: bool = unsafe { msg_send![*self.ns_window, isVisible] };
 
@@ -1111,6 +1619,7 @@ impl UnownedWindow {
                 util::make_key_and_order_front_async(*self.ns_window);
             }
         }
+        !is_minimized && is_visible
     }
 
     #[inline]
@@ -1131,6 +1640,19 @@ impl UnownedWindow {
     pub(crate) fn current_monitor_inner(&self) -> RootMonitorHandle {
         unsafe {
             let screen: id = msg_send![*self.ns_window, screen];
+            if screen == nil {
+                // The window is entirely off-screen, e.g. because it was restored to a monitor
+                // that has since been disconnected.
+                let frame = NSWindow::frame(*self.ns_window);
+                let top_left = util::top_left(frame);
+                let center = (
+                    top_left.x + frame.size.width as f64 / 2.0,
+                    top_left.y + frame.size.height as f64 / 2.0,
+                );
+                return RootMonitorHandle {
+                    inner: monitor::nearest_monitor(center),
+                };
+            }
             let desc = NSScreen::deviceDescription(screen);
             let key = util::ns_string_id_ref("NSScreenNumber");
             let value = NSDictionary::valueForKey_(desc, *key);
@@ -1189,155 +1711,1380 @@ impl WindowExtMacOS for UnownedWindow {
     }
 
     #[inline]
-    fn set_simple_fullscreen(&self, fullscreen: bool) -> bool {
-        let mut shared_state_lock = self.shared_state.lock().unwrap();
+    fn is_fullscreen_any(&self) -> bool {
+        let shared_state_lock = self.shared_state.lock().unwrap();
+        shared_state_lock.fullscreen.is_some() || shared_state_lock.is_simple_fullscreen
+    }
 
-        unsafe {
-            let app = NSApp();
-            let is_native_fullscreen = shared_state_lock.fullscreen.is_some();
-            let is_simple_fullscreen = shared_state_lock.is_simple_fullscreen;
+    #[inline]
+    fn set_simple_fullscreen(&self, fullscreen: bool) -> bool {
+        self.set_simple_fullscreen_mode(fullscreen, false)
+    }
 
-            // Do nothing if native fullscreen is active.
-            if is_native_fullscreen
-                || (fullscreen && is_simple_fullscreen)
-                || (!fullscreen && !is_simple_fullscreen)
-            {
-                return false;
-            }
+    #[inline]
+    fn borderless_fullscreen_on_current_space(&self) -> bool {
+        let shared_state_lock = self.shared_state.lock().unwrap();
+        shared_state_lock.is_simple_fullscreen
+            && shared_state_lock.simple_fullscreen_level.is_some()
+    }
 
-            if fullscreen {
-                // Remember the original window's settings
-                // Exclude title bar
-                shared_state_lock.standard_frame = Some(NSWindow::contentRectForFrameRect_(
-                    *self.ns_window,
-                    NSWindow::frame(*self.ns_window),
-                ));
-                shared_state_lock.saved_style = Some(self.ns_window.styleMask());
-                shared_state_lock.save_presentation_opts = Some(app.presentationOptions_());
+    #[inline]
+    fn set_borderless_fullscreen_on_current_space(&self, fullscreen: bool) -> bool {
+        self.set_simple_fullscreen_mode(fullscreen, true)
+    }
 
-                // Tell our window's state that we're in fullscreen
-                shared_state_lock.is_simple_fullscreen = true;
+    #[inline]
+    fn has_shadow(&self) -> bool {
+        unsafe { Bool::from_raw(self.ns_window.hasShadow()).as_bool() }
+    }
 
-                // Simulate pre-Lion fullscreen by hiding the dock and menu bar
-                let presentation_options =
-                    NSApplicationPresentationOptions::NSApplicationPresentationAutoHideDock |
-                    NSApplicationPresentationOptions::NSApplicationPresentationAutoHideMenuBar;
-                app.setPresentationOptions_(presentation_options);
+    #[inline]
+    fn set_has_shadow(&self, has_shadow: bool) {
+        unsafe { self.ns_window.setHasShadow_(Bool::new(has_shadow).as_raw()) }
+    }
 
-                // Hide the titlebar
-                util::toggle_style_mask(
-                    *self.ns_window,
-                    *self.ns_view,
-                    NSWindowStyleMask::NSTitledWindowMask,
-                    false,
-                );
+    #[inline]
+    fn drag_threshold(&self) -> Option<f64> {
+        unsafe { view::drag_threshold(*self.ns_view) }
+    }
 
-                // Set the window frame to the screen frame size
-                let screen = self.ns_window.screen();
-                let screen_frame = NSScreen::frame(screen);
-                NSWindow::setFrame_display_(*self.ns_window, screen_frame, Bool::YES.as_raw());
+    #[inline]
+    fn set_drag_threshold(&self, threshold: Option<f64>) {
+        unsafe { view::set_drag_threshold(*self.ns_view, threshold) }
+    }
 
-                // Fullscreen windows can't be resized, minimized, or moved
-                util::toggle_style_mask(
-                    *self.ns_window,
-                    *self.ns_view,
-                    NSWindowStyleMask::NSMiniaturizableWindowMask,
-                    false,
-                );
-                util::toggle_style_mask(
-                    *self.ns_window,
-                    *self.ns_view,
-                    NSWindowStyleMask::NSResizableWindowMask,
-                    false,
-                );
-                NSWindow::setMovable_(*self.ns_window, Bool::NO.as_raw());
+    #[inline]
+    fn ime_passthrough(&self) -> bool {
+        unsafe { view::ime_passthrough(*self.ns_view) }
+    }
 
-                true
+    #[inline]
+    fn set_ime_passthrough(&self, passthrough: bool) {
+        unsafe { view::set_ime_passthrough(*self.ns_view, passthrough) }
+    }
+
+    #[inline]
+    fn key_equivalent_policy(&self) -> KeyEquivalentPolicy {
+        unsafe { view::key_equivalent_policy(*self.ns_view) }
+    }
+
+    #[inline]
+    fn set_key_equivalent_policy(&self, policy: KeyEquivalentPolicy) {
+        unsafe { view::set_key_equivalent_policy(*self.ns_view, policy) }
+    }
+
+    #[inline]
+    fn set_key_equivalent_handler(
+        &self,
+        handler: Option<Box<dyn Fn(&KeyboardInput) -> KeyEquivalentPolicy + Send + Sync>>,
+    ) {
+        unsafe { view::set_key_equivalent_handler(*self.ns_view, handler.map(Arc::from)) }
+    }
+
+    #[inline]
+    fn alpha(&self) -> f64 {
+        unsafe { self.ns_window.alphaValue() as f64 }
+    }
+
+    #[inline]
+    fn set_alpha(&self, alpha: f64) {
+        unsafe {
+            self.ns_window
+                .setAlphaValue_(alpha.clamp(0.0, 1.0) as CGFloat)
+        }
+    }
+
+    #[inline]
+    fn is_cursor_visible_while_locked(&self) -> bool {
+        self.lock_shared_state("is_cursor_visible_while_locked")
+            .cursor_visible_while_locked
+    }
+
+    #[inline]
+    fn set_cursor_visible_while_locked(&self, visible: bool) {
+        let mut shared_state_lock = self.lock_shared_state("set_cursor_visible_while_locked");
+        shared_state_lock.cursor_visible_while_locked = visible;
+        let hidden_by_grab = cursor_hidden_by_grab(shared_state_lock.cursor_locked, visible);
+        drop(shared_state_lock);
+        self.update_cursor_state(|cursor_state| cursor_state.hidden_by_grab = hidden_by_grab);
+    }
+
+    #[inline]
+    fn corner_radius(&self) -> Option<f64> {
+        self.lock_shared_state("corner_radius").corner_radius
+    }
+
+    #[inline]
+    fn set_corner_radius(&self, radius: Option<f64>) {
+        self.lock_shared_state("set_corner_radius").corner_radius = radius;
+        unsafe { util::set_corner_radius_async(*self.ns_window, *self.ns_view, radius) };
+    }
+
+    #[inline]
+    fn is_movable(&self) -> bool {
+        unsafe { msg_send![*self.ns_window, isMovable] }
+    }
+
+    #[inline]
+    fn set_movable(&self, movable: bool) {
+        let mut shared_state_lock = self.lock_shared_state("set_movable");
+        shared_state_lock.movable = movable;
+        // Simple fullscreen windows can't be moved; the new value is applied
+        // when exiting it.
+        if !shared_state_lock.is_simple_fullscreen {
+            unsafe { NSWindow::setMovable_(*self.ns_window, Bool::new(movable).as_raw()) };
+        }
+    }
+
+    fn set_frame_animated<P: Into<Position>, S: Into<Size>>(
+        &self,
+        position: Option<P>,
+        size: Option<S>,
+        duration: f64,
+    ) {
+        let scale_factor = self.scale_factor();
+        let mut frame = unsafe { NSWindow::frame(*self.ns_window) };
+        if let Some(size) = size {
+            let size = size.into().to_logical::<f64>(scale_factor);
+            let mut content_rect =
+                unsafe { NSWindow::contentRectForFrameRect_(*self.ns_window, frame) };
+            content_rect.size = NSSize::new(size.width as CGFloat, size.height as CGFloat);
+            let new_frame =
+                unsafe { NSWindow::frameRectForContentRect_(*self.ns_window, content_rect) };
+            // The origin point of a rectangle is at its bottom left in Cocoa.
+            // To ensure the window's top-left point remains the same:
+            frame.origin.y += frame.size.height - new_frame.size.height;
+            frame.size = new_frame.size;
+        }
+        if let Some(position) = position {
+            let top_left = util::ns_point(position.into().to_logical(scale_factor));
+            frame.origin.x = top_left.x;
+            frame.origin.y = top_left.y - frame.size.height;
+        }
+        unsafe { util::set_frame_animated_async(*self.ns_window, frame, duration) };
+    }
+
+    #[inline]
+    fn is_display_link_paced(&self) -> bool {
+        self.lock_shared_state("is_display_link_paced")
+            .display_link
+            .is_some()
+    }
+
+    #[inline]
+    fn set_display_link_paced(&self, paced: bool) {
+        let mut shared_state_lock = self.lock_shared_state("set_display_link_paced");
+        if paced == shared_state_lock.display_link.is_some() {
+            return;
+        }
+        shared_state_lock.display_link = if paced {
+            let display_id = self.current_monitor_inner().inner.native_identifier();
+            DisplayLink::new(RootWindowId(self.id()), display_id)
+        } else {
+            None
+        };
+    }
+
+    #[inline]
+    fn set_dock_progress(&self, progress: Option<f64>) {
+        // `clamp` would propagate NaN, which `max` and `min` don't.
+        let progress = progress.map(|progress| progress.max(0.0).min(1.0));
+        unsafe { util::set_dock_progress_async(progress) };
+    }
+
+    fn begin_drag_session(
+        &self,
+        items: Vec<DragItem>,
+        image: Icon,
+        hotspot: (u32, u32),
+    ) -> Result<(), ExternalError> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        unsafe {
+            let event = drag_event(*self.ns_window);
+            if event == nil {
+                return Err(ExternalError::Os(os_error!(OsError::CreationError(
+                    "Couldn't create an `NSEvent` to start the drag with"
+                ))));
+            }
+
+            // The image is sized in points, so that it's shown at its native
+            // resolution, and positioned so that the hotspot is under the cursor.
+            // The view's coordinate system has its origin at the bottom-left.
+            let scale_factor = NSWindow::backingScaleFactor(*self.ns_window) as f64;
+            let ns_image = util::ns_image_from_rgba(&image.inner, scale_factor);
+            let image_size = NSSize::new(
+                (image.inner.width as f64 / scale_factor) as CGFloat,
+                (image.inner.height as f64 / scale_factor) as CGFloat,
+            );
+            let location = NSWindow::mouseLocationOutsideOfEventStream(*self.ns_window);
+            let location: NSPoint = msg_send![*self.ns_view, convertPoint: location, fromView: nil];
+            let frame = NSRect::new(
+                NSPoint::new(
+                    location.x - (hotspot.0 as f64 / scale_factor) as CGFloat,
+                    location.y - image_size.height + (hotspot.1 as f64 / scale_factor) as CGFloat,
+                ),
+                image_size,
+            );
+
+            let dragging_items: id = msg_send![class!(NSMutableArray), array];
+            for item in items {
+                let writer = match item {
+                    DragItem::File(path) => {
+                        let path = util::ns_string_id_ref(&path.to_string_lossy());
+                        IdRef::retain(msg_send![class!(NSURL), fileURLWithPath: *path])
+                    }
+                    DragItem::Text(text) => util::ns_string_id_ref(&text),
+                };
+                let dragging_item: id = msg_send![class!(NSDraggingItem), alloc];
+                let dragging_item =
+                    IdRef::new(msg_send![dragging_item, initWithPasteboardWriter: *writer]);
+                let _: () = msg_send![*dragging_item, setDraggingFrame: frame, contents: *ns_image];
+                let _: () = msg_send![dragging_items, addObject: *dragging_item];
+            }
+
+            // The view is the dragging source, see `draggingSession:endedAtPoint:operation:`.
+            let _: id = msg_send![
+                *self.ns_view,
+                beginDraggingSessionWithItems: dragging_items,
+                event: event,
+                source: *self.ns_view,
+            ];
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn set_title_line_break_mode(&self, mode: TitleLineBreakMode) {
+        let line_break_mode = match mode {
+            TitleLineBreakMode::TruncateHead => ffi::NSLineBreakByTruncatingHead,
+            TitleLineBreakMode::TruncateMiddle => ffi::NSLineBreakByTruncatingMiddle,
+            TitleLineBreakMode::TruncateTail => ffi::NSLineBreakByTruncatingTail,
+            TitleLineBreakMode::Clip => ffi::NSLineBreakByClipping,
+        };
+        unsafe { util::set_title_line_break_mode_async(*self.ns_window, line_break_mode) };
+    }
+
+    #[inline]
+    fn is_cursor_hittest_enabled(&self) -> bool {
+        let ignores_mouse_events: bool = unsafe { msg_send![*self.ns_window, ignoresMouseEvents] };
+        !ignores_mouse_events
+    }
+
+    #[inline]
+    fn window_level_raw(&self) -> i32 {
+        unsafe { self.ns_window.level() as i32 }
+    }
+
+    #[inline]
+    fn set_window_level_raw(&self, level: i32) {
+        unsafe { util::set_raw_level_async(*self.ns_window, level as NSInteger) };
+    }
+
+    #[inline]
+    fn set_window_level_preset(&self, preset: WindowLevelPreset) {
+        let key = window_level_preset_to_key(preset) as ffi::CGWindowLevelKey;
+        self.set_window_level_raw(unsafe { ffi::CGWindowLevelForKey(key) });
+    }
+
+    #[inline]
+    fn titlebar_buttons_hidden(&self) -> bool {
+        unsafe {
+            let button = self
+                .ns_window
+                .standardWindowButton_(NSWindowButton::NSWindowCloseButton);
+            // There are no buttons without a titlebar.
+            button == nil || msg_send![button, isHidden]
+        }
+    }
+
+    #[inline]
+    fn set_titlebar_buttons_hidden(&self, hidden: bool) {
+        unsafe { util::set_titlebar_buttons_hidden_async(*self.ns_window, hidden) };
+    }
+
+    #[inline]
+    fn is_titlebar_transparent(&self) -> bool {
+        unsafe { msg_send![*self.ns_window, titlebarAppearsTransparent] }
+    }
+
+    #[inline]
+    fn set_titlebar_transparent(&self, transparent: bool) {
+        unsafe {
+            self.ns_window
+                .setTitlebarAppearsTransparent_(Bool::new(transparent).as_raw())
+        };
+    }
+
+    #[inline]
+    fn set_accent_color(&self, color: Option<(f64, f64, f64, f64)>) {
+        unsafe { util::set_accent_color_async(*self.ns_view, color) };
+    }
+
+    #[inline]
+    fn tabbing_mode(&self) -> TabbingMode {
+        let tabbing_mode: ffi::NSWindowTabbingMode =
+            unsafe { msg_send![*self.ns_window, tabbingMode] };
+        match tabbing_mode {
+            ffi::NSWindowTabbingModePreferred => TabbingMode::Preferred,
+            ffi::NSWindowTabbingModeDisallowed => TabbingMode::Disallowed,
+            _ => TabbingMode::Automatic,
+        }
+    }
+
+    #[inline]
+    fn set_tabbing_mode(&self, tabbing_mode: TabbingMode) {
+        let tabbing_mode = tabbing_mode_to_ns(tabbing_mode);
+        unsafe { msg_send![*self.ns_window, setTabbingMode: tabbing_mode] }
+    }
+
+    #[inline]
+    fn is_excluded_from_windows_menu(&self) -> bool {
+        unsafe { msg_send![*self.ns_window, isExcludedFromWindowsMenu] }
+    }
+
+    #[inline]
+    fn set_excluded_from_windows_menu(&self, excluded: bool) {
+        unsafe { msg_send![*self.ns_window, setExcludedFromWindowsMenu: excluded] }
+    }
+
+    #[inline]
+    fn is_excluded_from_mission_control(&self) -> bool {
+        let behavior = unsafe { self.ns_window.collectionBehavior() };
+        behavior.contains(NSWindowCollectionBehavior::NSWindowCollectionBehaviorTransient)
+    }
+
+    #[inline]
+    fn set_excluded_from_mission_control(&self, excluded: bool) {
+        // `Managed`, `Transient` and `Stationary` are mutually exclusive, as are
+        // `ParticipatesInCycle` and `IgnoresCycle`.
+        let excluded_behavior = NSWindowCollectionBehavior::NSWindowCollectionBehaviorTransient
+            | NSWindowCollectionBehavior::NSWindowCollectionBehaviorIgnoresCycle;
+        let conflicting_behavior = NSWindowCollectionBehavior::NSWindowCollectionBehaviorManaged
+            | NSWindowCollectionBehavior::NSWindowCollectionBehaviorStationary
+            | NSWindowCollectionBehavior::NSWindowCollectionBehaviorParticipatesInCycle;
+        unsafe {
+            let mut behavior = self.ns_window.collectionBehavior();
+            if excluded {
+                behavior.remove(conflicting_behavior);
+                behavior.insert(excluded_behavior);
             } else {
-                let new_mask = self.saved_style(&mut *shared_state_lock);
-                self.set_style_mask_async(new_mask);
-                shared_state_lock.is_simple_fullscreen = false;
+                behavior.remove(excluded_behavior);
+            }
+            self.ns_window.setCollectionBehavior_(behavior);
+        }
+    }
 
-                if let Some(presentation_opts) = shared_state_lock.save_presentation_opts {
-                    app.setPresentationOptions_(presentation_opts);
-                }
+    #[inline]
+    fn is_fullscreen_auxiliary(&self) -> bool {
+        let behavior = unsafe { self.ns_window.collectionBehavior() };
+        behavior.contains(NSWindowCollectionBehavior::NSWindowCollectionBehaviorFullScreenAuxiliary)
+    }
 
-                let frame = shared_state_lock.saved_standard_frame();
-                NSWindow::setFrame_display_(*self.ns_window, frame, Bool::YES.as_raw());
-                NSWindow::setMovable_(*self.ns_window, Bool::YES.as_raw());
+    #[inline]
+    fn set_fullscreen_auxiliary(&self, auxiliary: bool) {
+        // `FullScreenPrimary` and `FullScreenAuxiliary` are mutually exclusive,
+        // as are `CanJoinAllSpaces` and `MoveToActiveSpace`.
+        let auxiliary_behavior =
+            NSWindowCollectionBehavior::NSWindowCollectionBehaviorFullScreenAuxiliary
+                | NSWindowCollectionBehavior::NSWindowCollectionBehaviorMoveToActiveSpace;
+        let conflicting_behavior =
+            NSWindowCollectionBehavior::NSWindowCollectionBehaviorFullScreenPrimary
+                | NSWindowCollectionBehavior::NSWindowCollectionBehaviorCanJoinAllSpaces;
+        unsafe {
+            let mut behavior = self.ns_window.collectionBehavior();
+            if auxiliary {
+                behavior.remove(conflicting_behavior);
+                behavior.insert(auxiliary_behavior);
+            } else {
+                behavior.remove(auxiliary_behavior);
+            }
+            self.ns_window.setCollectionBehavior_(behavior);
+        }
+    }
 
-                true
+    #[inline]
+    fn redraws_while_occluded(&self) -> bool {
+        self.lock_shared_state("redraws_while_occluded")
+            .redraw_while_occluded
+    }
+
+    #[inline]
+    fn set_redraw_while_occluded(&self, enabled: bool) {
+        let mut shared_state_lock = self.lock_shared_state("set_redraw_while_occluded");
+        shared_state_lock.redraw_while_occluded = enabled;
+        let redraw_deferred = enabled && mem::take(&mut shared_state_lock.redraw_deferred);
+        drop(shared_state_lock);
+        if redraw_deferred {
+            self.request_redraw();
+        }
+    }
+
+    #[inline]
+    fn surface_scale(&self) -> f64 {
+        let surface_scale = self.lock_shared_state("surface_scale").surface_scale;
+        surface_scale.unwrap_or_else(|| self.scale_factor())
+    }
+
+    #[inline]
+    fn set_surface_scale(&self, scale: f64) {
+        assert!(
+            validate_scale_factor(scale),
+            "surface scale must be positive and finite"
+        );
+        self.lock_shared_state("set_surface_scale").surface_scale = Some(scale);
+        unsafe { util::set_contents_scale_async(*self.ns_view, scale) };
+    }
+
+    #[inline]
+    fn set_blur_material(&self, material: Option<BlurMaterial>) {
+        let mut shared_state_lock = self.lock_shared_state("set_blur_material");
+        shared_state_lock.blur_material = material;
+        let blending_mode = blending_mode_to_ns(shared_state_lock.blur_blending_mode);
+        unsafe {
+            util::set_visual_effect_async(
+                *self.ns_window,
+                material.map(blur_material_to_ns),
+                blending_mode,
+            )
+        };
+    }
+
+    #[inline]
+    fn set_blur_blending_mode(&self, blending_mode: BlurBlendingMode) {
+        let mut shared_state_lock = self.lock_shared_state("set_blur_blending_mode");
+        shared_state_lock.blur_blending_mode = blending_mode;
+        if let Some(material) = shared_state_lock.blur_material {
+            unsafe {
+                util::set_visual_effect_async(
+                    *self.ns_window,
+                    Some(blur_material_to_ns(material)),
+                    blending_mode_to_ns(blending_mode),
+                )
+            };
+        }
+    }
+
+    #[inline]
+    fn effective_appearance_name(&self) -> String {
+        unsafe {
+            // `effectiveAppearance` is only available on macOS 10.14 and later,
+            // before which there's only the one appearance.
+            let responds: bool = msg_send![
+                *self.ns_window,
+                respondsToSelector: sel!(effectiveAppearance)
+            ];
+            if !responds {
+                return "NSAppearanceNameAqua".to_owned();
             }
+            let appearance: id = msg_send![*self.ns_window, effectiveAppearance];
+            let name: id = msg_send![appearance, name];
+            util::id_to_string_lossy(name)
         }
     }
 
     #[inline]
-    fn has_shadow(&self) -> bool {
-        unsafe { Bool::from_raw(self.ns_window.hasShadow()).as_bool() }
+    fn theme(&self) -> Theme {
+        appearance_name_to_theme(&self.effective_appearance_name())
     }
 
     #[inline]
-    fn set_has_shadow(&self, has_shadow: bool) {
-        unsafe { self.ns_window.setHasShadow_(Bool::new(has_shadow).as_raw()) }
+    fn set_theme(&self, theme: Option<Theme>) {
+        unsafe { util::set_appearance_async(*self.ns_window, theme) };
+    }
+
+    #[inline]
+    fn is_constrained_to_monitor(&self) -> bool {
+        self.lock_shared_state("is_constrained_to_monitor")
+            .constrained_to_monitor
+    }
+
+    #[inline]
+    fn set_constrained_to_monitor(&self, constrained: bool) {
+        self.lock_shared_state("set_constrained_to_monitor")
+            .constrained_to_monitor = constrained;
+        self.apply_max_inner_size();
+    }
+
+    #[inline]
+    fn synchronous_resize(&self) -> bool {
+        unsafe { view::synchronous_resize(*self.ns_view) }
+    }
+
+    #[inline]
+    fn set_synchronous_resize(&self, synchronous_resize: bool) {
+        unsafe { view::set_synchronous_resize(*self.ns_view, synchronous_resize) }
+    }
+
+    #[inline]
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        unsafe { util::close_async(self.ns_window.clone()) };
+    }
+
+    #[inline]
+    fn set_accessibility_label(&self, label: &str) {
+        unsafe { util::set_accessibility_label_async(*self.ns_window, label.to_string()) };
+    }
+
+    #[inline]
+    fn set_accessibility_role(&self, role: &str) {
+        unsafe { util::set_accessibility_role_async(*self.ns_view, role.to_string()) };
+    }
+
+    #[inline]
+    fn set_content_size_limits(&self, min: Option<Size>, max: Option<Size>) {
+        // The maximum goes first, so that the new minimum isn't clamped down
+        // to the previous maximum.
+        self.set_max_inner_size(max);
+        self.set_min_inner_size(min);
+    }
+
+    fn safe_area_insets(&self) -> SafeAreaInsets {
+        unsafe {
+            let bounds: NSRect = msg_send![*self.ns_view, bounds];
+            let responds: bool = msg_send![
+                *self.ns_view,
+                respondsToSelector: sel!(safeAreaRect)
+            ];
+            // Before macOS 11, there's no notch, and only the title bar can
+            // overlap the view, see `titlebar_height`.
+            let safe_rect: NSRect = if responds {
+                msg_send![*self.ns_view, safeAreaRect]
+            } else {
+                let layout_rect: NSRect = msg_send![*self.ns_window, contentLayoutRect];
+                let layout_rect: NSRect =
+                    msg_send![*self.ns_view, convertRect: layout_rect, fromView: nil];
+                NSRect::new(
+                    NSPoint::new(
+                        layout_rect.origin.x.max(bounds.origin.x),
+                        layout_rect.origin.y.max(bounds.origin.y),
+                    ),
+                    NSSize::new(
+                        layout_rect.size.width.min(bounds.size.width),
+                        layout_rect.size.height.min(bounds.size.height),
+                    ),
+                )
+            };
+            insets_of(bounds, safe_rect, self.scale_factor())
+        }
+    }
+
+    fn inner_size_excluding_insets(&self) -> PhysicalSize<u32> {
+        let size = self.inner_size();
+        let insets = self.safe_area_insets();
+        PhysicalSize::new(
+            size.width.saturating_sub(insets.left + insets.right),
+            size.height.saturating_sub(insets.top + insets.bottom),
+        )
+    }
+
+    #[inline]
+    fn confine_cursor_to_rect(&self, rect: Option<(Position, Size)>) {
+        let scale_factor = self.scale_factor();
+        let rect = rect.map(|(position, size)| {
+            (
+                position.to_logical(scale_factor),
+                size.to_logical(scale_factor),
+            )
+        });
+        unsafe { view::set_cursor_confine_rect(*self.ns_view, rect) };
+    }
+
+    #[inline]
+    fn is_cursor_icon_supported(&self, icon: CursorIcon) -> bool {
+        NSCursor::is_supported(icon)
+    }
+
+    #[inline]
+    fn titlebar_height(&self) -> f64 {
+        // Unlike `contentRectForFrameRect:`, which covers the whole frame with
+        // a full-size content view, the layout rect always excludes the title
+        // bar and the toolbar.
+        unsafe {
+            let frame = NSWindow::frame(*self.ns_window);
+            let layout_rect: NSRect = msg_send![*self.ns_window, contentLayoutRect];
+            titlebar_height_of(frame, layout_rect)
+        }
+    }
+
+    #[inline]
+    fn allow_hidpi(&self) -> bool {
+        unsafe { msg_send![*self.ns_view, wantsBestResolutionOpenGLSurface] }
+    }
+
+    #[inline]
+    fn set_allow_hidpi(&self, allow_hidpi: bool) {
+        if self.allow_hidpi() == allow_hidpi {
+            return;
+        }
+        unsafe {
+            util::set_wants_best_resolution_opengl_surface_async(
+                *self.ns_window,
+                *self.ns_view,
+                allow_hidpi,
+            );
+        }
+    }
+
+    #[inline]
+    fn is_sheet(&self) -> bool {
+        let parent: id = unsafe { msg_send![*self.ns_window, sheetParent] };
+        parent != nil
+    }
+
+    #[inline]
+    fn begin_as_sheet(&self, parent: &RootWindow) {
+        unsafe { util::begin_sheet_async(*self.ns_window, *parent.window.ns_window) };
+    }
+
+    #[inline]
+    fn end_sheet(&self) {
+        unsafe { util::end_sheet_async(*self.ns_window) };
+    }
+
+    #[inline]
+    fn prevents_termination_when_modal(&self) -> bool {
+        unsafe { msg_send![*self.ns_window, preventsApplicationTerminationWhenModal] }
+    }
+
+    #[inline]
+    fn set_prevents_termination_when_modal(&self, prevents: bool) {
+        unsafe {
+            let _: () = msg_send![
+                *self.ns_window,
+                setPreventsApplicationTerminationWhenModal: prevents
+            ];
+        }
     }
 }
 
 impl Drop for UnownedWindow {
     fn drop(&mut self) {
         trace!("Dropping `UnownedWindow` ({:?})", self as *mut _);
-        // Close the window if it has not yet been closed.
+        // Close the window if it has not yet been closed. Closing it again
+        // after `close` does nothing.
         if *self.ns_window != nil {
             unsafe { util::close_async(self.ns_window.clone()) };
         }
     }
 }
 
-unsafe fn set_min_inner_size<V: NSWindow + Copy>(window: V, mut min_size: LogicalSize<f64>) {
-    let mut current_rect = NSWindow::frame(window);
-    let content_rect = NSWindow::contentRectForFrameRect_(window, NSWindow::frame(window));
-    // Convert from client area size to window size
-    min_size.width += (current_rect.size.width - content_rect.size.width) as f64; // this tends to be 0
-    min_size.height += (current_rect.size.height - content_rect.size.height) as f64;
-    let min_size = NSSize {
-        width: min_size.width as CGFloat,
-        height: min_size.height as CGFloat,
+// Returns the event to start a drag with, which may be `nil`.
+//
+// Drags can only be started from a mouse down. This includes the mouse events
+// that trackpads synthesize, e.g. with three-finger drag enabled, which have
+// the touch subtype.
+//
+// `performWindowDragWithEvent:` may ignore a mouse dragged event, which is the
+// current event when the application only starts the drag once the mouse moved
+// past a threshold of its own. It's replaced with a mouse down of the same
+// button at the same location, from which the window then follows the mouse.
+// Anything else (no event, a gesture, a key press, ...) is replaced with a left
+// mouse down at the current cursor location.
+unsafe fn drag_event(ns_window: id) -> id {
+    let event: id = msg_send![NSApp(), currentEvent];
+    if !is_drag_event(event) {
+        let location = NSWindow::mouseLocationOutsideOfEventStream(ns_window);
+        return mouse_down_event(ns_window, appkit::NSLeftMouseDown, location, event);
+    }
+    let event_type = match event.eventType() {
+        appkit::NSLeftMouseDragged => appkit::NSLeftMouseDown,
+        appkit::NSRightMouseDragged => appkit::NSRightMouseDown,
+        appkit::NSOtherMouseDragged => appkit::NSOtherMouseDown,
+        _ => return event,
+    };
+    mouse_down_event(ns_window, event_type, event.locationInWindow(), event)
+}
+
+// Creates a mouse down at `location` in the window, with the modifiers and the
+// timestamp of `event` if it's not `nil`.
+unsafe fn mouse_down_event(
+    ns_window: id,
+    event_type: NSEventType,
+    location: NSPoint,
+    event: id,
+) -> id {
+    let (modifiers, timestamp) = if event == nil {
+        (NSEventModifierFlags::empty(), 0.0)
+    } else {
+        (event.modifierFlags(), event.timestamp())
     };
-    window.setMinSize_(min_size);
-    // If necessary, resize the window to match constraint
-    if current_rect.size.width < min_size.width {
-        current_rect.size.width = min_size.width;
-        window.setFrame_display_(current_rect, Bool::NO.as_raw())
-    }
-    if current_rect.size.height < min_size.height {
-        // The origin point of a rectangle is at its bottom left in Cocoa.
-        // To ensure the window's top-left point remains the same:
-        current_rect.origin.y += current_rect.size.height - min_size.height;
-        current_rect.size.height = min_size.height;
-        window.setFrame_display_(current_rect, Bool::NO.as_raw())
+    let window_number: NSInteger = msg_send![ns_window, windowNumber];
+    NSEvent::mouseEventWithType_location_modifierFlags_timestamp_windowNumber_context_eventNumber_clickCount_pressure_(
+        nil,
+        event_type,
+        location,
+        modifiers,
+        timestamp,
+        window_number,
+        nil,
+        0,
+        1,
+        1.0,
+    )
+}
+
+unsafe fn is_drag_event(event: id) -> bool {
+    if event == nil {
+        return false;
+    }
+    match event.eventType() {
+        appkit::NSLeftMouseDown
+        | appkit::NSLeftMouseDragged
+        | appkit::NSRightMouseDown
+        | appkit::NSRightMouseDragged
+        | appkit::NSOtherMouseDown
+        | appkit::NSOtherMouseDragged => {
+            // Tablet proximity events are sent when a pen enters or leaves the
+            // tablet, rather than when it's pressed or moved.
+            let subtype: i16 = msg_send![event, subtype];
+            subtype != ffi::NSEventSubtypeTabletProximity
+        }
+        _ => false,
     }
 }
 
-unsafe fn set_max_inner_size<V: NSWindow + Copy>(window: V, mut max_size: LogicalSize<f64>) {
-    let mut current_rect = NSWindow::frame(window);
-    let content_rect = NSWindow::contentRectForFrameRect_(window, NSWindow::frame(window));
-    // Convert from client area size to window size
-    max_size.width += (current_rect.size.width - content_rect.size.width) as f64; // this tends to be 0
-    max_size.height += (current_rect.size.height - content_rect.size.height) as f64;
+fn blur_material_to_ns(material: BlurMaterial) -> ffi::NSVisualEffectMaterial {
+    match material {
+        BlurMaterial::Titlebar => ffi::NSVisualEffectMaterialTitlebar,
+        BlurMaterial::Selection => ffi::NSVisualEffectMaterialSelection,
+        BlurMaterial::Menu => ffi::NSVisualEffectMaterialMenu,
+        BlurMaterial::Popover => ffi::NSVisualEffectMaterialPopover,
+        BlurMaterial::Sidebar => ffi::NSVisualEffectMaterialSidebar,
+        BlurMaterial::HeaderView => ffi::NSVisualEffectMaterialHeaderView,
+        BlurMaterial::Sheet => ffi::NSVisualEffectMaterialSheet,
+        BlurMaterial::WindowBackground => ffi::NSVisualEffectMaterialWindowBackground,
+        BlurMaterial::HudWindow => ffi::NSVisualEffectMaterialHUDWindow,
+        BlurMaterial::FullScreenUi => ffi::NSVisualEffectMaterialFullScreenUI,
+        BlurMaterial::ToolTip => ffi::NSVisualEffectMaterialToolTip,
+        BlurMaterial::ContentBackground => ffi::NSVisualEffectMaterialContentBackground,
+        BlurMaterial::UnderWindowBackground => ffi::NSVisualEffectMaterialUnderWindowBackground,
+        BlurMaterial::UnderPageBackground => ffi::NSVisualEffectMaterialUnderPageBackground,
+    }
+}
+
+fn blending_mode_to_ns(blending_mode: BlurBlendingMode) -> ffi::NSVisualEffectBlendingMode {
+    match blending_mode {
+        BlurBlendingMode::BehindWindow => ffi::NSVisualEffectBlendingModeBehindWindow,
+        BlurBlendingMode::WithinWindow => ffi::NSVisualEffectBlendingModeWithinWindow,
+    }
+}
+
+fn window_level_preset_to_key(preset: WindowLevelPreset) -> NSInteger {
+    match preset {
+        WindowLevelPreset::Normal => ffi::kCGNormalWindowLevelKey,
+        WindowLevelPreset::Floating => ffi::kCGFloatingWindowLevelKey,
+        WindowLevelPreset::Status => ffi::kCGStatusWindowLevelKey,
+        WindowLevelPreset::PopUpMenu => ffi::kCGPopUpMenuWindowLevelKey,
+        WindowLevelPreset::ScreenSaver => ffi::kCGScreenSaverWindowLevelKey,
+        WindowLevelPreset::Maximum => ffi::kCGMaximumWindowLevelKey,
+    }
+}
+
+fn tabbing_mode_to_ns(tabbing_mode: TabbingMode) -> ffi::NSWindowTabbingMode {
+    match tabbing_mode {
+        TabbingMode::Automatic => ffi::NSWindowTabbingModeAutomatic,
+        TabbingMode::Preferred => ffi::NSWindowTabbingModePreferred,
+        TabbingMode::Disallowed => ffi::NSWindowTabbingModeDisallowed,
+    }
+}
+
+// The position is offset from `outer_position` rather than converted on its own,
+// so that the two are exactly the same when the content fills the frame, e.g.
+// without decorations, instead of possibly rounding to different pixels.
+/// What's queued on the main thread when exiting fullscreen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FullscreenExitStep {
+    StyleMask,
+    Frame,
+    Maximized,
+    SizeConstraints,
+}
+
+// The style mask goes first, as it changes the frame and the content size. A
+// maximized window is zoomed again instead of getting its frame back, and the
+// constraints, which may have changed while the window was fullscreen, are
+// applied last, to the restored frame.
+fn fullscreen_exit_steps(maximized: bool) -> &'static [FullscreenExitStep] {
+    use FullscreenExitStep::*;
+    if maximized {
+        &[StyleMask, Maximized, SizeConstraints]
+    } else {
+        &[StyleMask, Frame, Maximized, SizeConstraints]
+    }
+}
+
+// Returns the mask that makes a window minimizable, or `None` if it already is.
+fn minimizable_mask(mask: NSWindowStyleMask) -> Option<NSWindowStyleMask> {
+    let required = NSWindowStyleMask::NSMiniaturizableWindowMask;
+    if mask.contains(required) {
+        None
+    } else {
+        Some(mask | required)
+    }
+}
+
+fn titlebar_height_of(frame: NSRect, layout_rect: NSRect) -> f64 {
+    (frame.size.height - layout_rect.size.height).max(0.0)
+}
+
+fn cursor_hidden_by_grab(cursor_locked: bool, visible_while_locked: bool) -> bool {
+    cursor_locked && !visible_while_locked
+}
+
+// The grab of a window that isn't key is suspended, and only applied again
+// once the window becomes key, see `set_cursor_grab_suspended`.
+fn should_relock_cursor(cursor_locked: bool, is_key_window: bool) -> bool {
+    cursor_locked && is_key_window
+}
+
+fn inner_position_from_outer(
+    frame_top_left: LogicalPosition<f64>,
+    content_top_left: LogicalPosition<f64>,
+    scale_factor: f64,
+) -> PhysicalPosition<i32> {
+    let outer_position: PhysicalPosition<i32> = frame_top_left.to_physical(scale_factor);
+    let offset: PhysicalPosition<i32> = LogicalPosition::new(
+        content_top_left.x - frame_top_left.x,
+        content_top_left.y - frame_top_left.y,
+    )
+    .to_physical(scale_factor);
+    PhysicalPosition::new(outer_position.x + offset.x, outer_position.y + offset.y)
+}
+
+// The insets of `safe_rect` within `bounds`, in physical pixels.
+fn insets_of(bounds: NSRect, safe_rect: NSRect, scale_factor: f64) -> SafeAreaInsets {
+    let to_physical = |inset: CGFloat| (inset.max(0.0) * scale_factor).round() as u32;
+    // The view isn't flipped, so its origin is at the bottom left.
+    SafeAreaInsets {
+        top: to_physical(
+            bounds.origin.y + bounds.size.height - (safe_rect.origin.y + safe_rect.size.height),
+        ),
+        left: to_physical(safe_rect.origin.x - bounds.origin.x),
+        bottom: to_physical(safe_rect.origin.y - bounds.origin.y),
+        right: to_physical(
+            bounds.origin.x + bounds.size.width - (safe_rect.origin.x + safe_rect.size.width),
+        ),
+    }
+}
+
+fn appearance_name_to_theme(name: &str) -> Theme {
+    // Also covers the accessibility variants of the dark appearance.
+    if name.contains("Dark") {
+        Theme::Dark
+    } else {
+        Theme::Light
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Bound {
+    Min,
+    Max,
+}
+
+// AppKit keeps the content size of windows at whole points, so a physical size
+// constraint that doesn't fall on whole points can't be honored exactly, and
+// converting it to the nearest points may land a pixel past it, e.g. a minimum
+// width of 1280 pixels at a scale factor of 1.5 would become 854 points, i.e.
+// 1281 pixels. Instead, the constraint is converted to the closest whole points
+// whose size in pixels, as reported by `inner_size`, doesn't exceed it: the
+// smallest ones that round to at least the minimum, and the largest ones that
+// round to at most the maximum.
+fn size_constraint_to_logical(size: Size, scale_factor: f64, bound: Bound) -> LogicalSize<f64> {
+    match size {
+        Size::Logical(size) => size,
+        Size::Physical(size) => {
+            let to_points = |pixels: u32| {
+                let pixels = pixels as f64;
+                match bound {
+                    Bound::Min => ((pixels - 0.5) / scale_factor).ceil().max(0.0),
+                    Bound::Max => ((pixels + 0.5) / scale_factor).ceil() - 1.0,
+                }
+            };
+            LogicalSize::new(to_points(size.width), to_points(size.height))
+        }
+    }
+}
+
+// These must be called on the main thread: neither `setContentMinSize:`/
+// `setContentMaxSize:` nor the resize that they may require are thread-safe,
+// and the `Resized` event can only be queued from there. See
+// `util::set_min_inner_size_sync`.
+//
+// The constraints are set on the content size rather than on the frame size, so
+// that AppKit keeps them in terms of the inner size when the decorations change
+// (e.g. the titlebar is added by `set_decorations`). Converting to a frame size
+// up front would leave the content short by the titlebar height in that case.
+//
+// AppKit's behavior is undefined when the minimum exceeds the maximum, so the
+// minimum is always clamped down to the maximum, in each dimension.
+pub(crate) unsafe fn set_min_inner_size<V: NSWindow + Copy>(window: V, min_size: LogicalSize<f64>) {
+    let min_size = NSSize::new(min_size.width as CGFloat, min_size.height as CGFloat);
+    window.setContentMinSize_(min_size_within(min_size, NSWindow::contentMaxSize(window)));
+    apply_inner_size_constraints(window);
+}
+
+pub(crate) unsafe fn set_max_inner_size<V: NSWindow + Copy>(window: V, max_size: LogicalSize<f64>) {
     let max_size = NSSize {
         width: max_size.width as CGFloat,
         height: max_size.height as CGFloat,
     };
-    window.setMaxSize_(max_size);
-    // If necessary, resize the window to match constraint
-    if current_rect.size.width > max_size.width {
-        current_rect.size.width = max_size.width;
-        window.setFrame_display_(current_rect, Bool::NO.as_raw())
-    }
-    if current_rect.size.height > max_size.height {
-        // The origin point of a rectangle is at its bottom left in Cocoa.
-        // To ensure the window's top-left point remains the same:
-        current_rect.origin.y += current_rect.size.height - max_size.height;
-        current_rect.size.height = max_size.height;
-        window.setFrame_display_(current_rect, Bool::NO.as_raw())
+    window.setContentMaxSize_(max_size);
+    let min_size = NSWindow::contentMinSize(window);
+    if min_size.width > max_size.width || min_size.height > max_size.height {
+        window.setContentMinSize_(min_size_within(min_size, max_size));
+    }
+    apply_inner_size_constraints(window);
+}
+
+// Clamps `min_size` down to `max_size`, in each dimension.
+fn min_size_within(min_size: NSSize, max_size: NSSize) -> NSSize {
+    NSSize::new(
+        min_size.width.min(max_size.width),
+        min_size.height.min(max_size.height),
+    )
+}
+
+// Increments smaller than a point can't be honored, as the window is resized by
+// whole points, so they're raised to one point.
+fn resize_increments_to_logical(increments: Size, scale_factor: f64) -> LogicalSize<f64> {
+    let logical = increments.to_logical::<f64>(scale_factor);
+    LogicalSize::new(logical.width.max(1.0), logical.height.max(1.0))
+}
+
+// Clamps `size` to `min_size` and `max_size`, in each dimension.
+pub(crate) fn clamp_content_size(size: NSSize, min_size: NSSize, max_size: NSSize) -> NSSize {
+    NSSize::new(
+        size.width.min(max_size.width).max(min_size.width),
+        size.height.min(max_size.height).max(min_size.height),
+    )
+}
+
+// Resizes the window to fit within `contentMinSize` and `contentMaxSize`, if
+// necessary, keeping its top-left corner in place.
+//
+// A fullscreen window keeps filling the screen: constraints changed while in
+// fullscreen are applied once it's exited, see `restore_state_from_fullscreen`.
+pub(crate) unsafe fn apply_inner_size_constraints<V: NSWindow + Copy>(window: V) {
+    if window
+        .styleMask()
+        .contains(NSWindowStyleMask::NSFullScreenWindowMask)
+    {
+        return;
+    }
+    let content_rect = NSWindow::contentRectForFrameRect_(window, NSWindow::frame(window));
+    let content_rect = match constrained_content_rect(
+        content_rect,
+        NSWindow::contentMinSize(window),
+        NSWindow::contentMaxSize(window),
+    ) {
+        Some(content_rect) => content_rect,
+        None => return,
+    };
+    // Resize both dimensions at once, so that only a single `Resized` event is
+    // emitted (from the view's `frameDidChange:`).
+    let frame = NSWindow::frameRectForContentRect_(window, content_rect);
+    window.setFrame_display_(frame, Bool::NO.as_raw())
+}
+
+// Returns `content_rect` resized to fit within `min_size` and `max_size` with
+// its top-left corner in place, or `None` if it already fits.
+fn constrained_content_rect(
+    mut content_rect: NSRect,
+    min_size: NSSize,
+    max_size: NSSize,
+) -> Option<NSRect> {
+    let NSSize { width, height } = clamp_content_size(content_rect.size, min_size, max_size);
+    if width == content_rect.size.width && height == content_rect.size.height {
+        return None;
+    }
+    // The origin point of a rectangle is at its bottom left in Cocoa.
+    // To ensure the window's top-left point remains the same:
+    content_rect.origin.y += content_rect.size.height - height;
+    content_rect.size = NSSize::new(width, height);
+    Some(content_rect)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_logical(width: u32, height: u32, scale_factor: f64, bound: Bound) -> (f64, f64) {
+        let size = size_constraint_to_logical(
+            PhysicalSize::new(width, height).into(),
+            scale_factor,
+            bound,
+        );
+        (size.width, size.height)
+    }
+
+    // The size in pixels reported by `inner_size` for a size in points.
+    fn to_pixels(points: f64, scale_factor: f64) -> u32 {
+        LogicalSize::new(points, points)
+            .to_physical::<u32>(scale_factor)
+            .width
+    }
+
+    #[test]
+    fn size_constraint_to_logical_stays_within_the_pixels() {
+        assert_eq!(to_logical(1280, 720, 1.5, Bound::Min), (853.0, 480.0));
+        assert_eq!(to_logical(1280, 720, 1.5, Bound::Max), (853.0, 480.0));
+        assert_eq!(to_logical(1281, 721, 1.5, Bound::Min), (854.0, 481.0));
+        assert_eq!(to_logical(1281, 721, 1.5, Bound::Max), (854.0, 480.0));
+        assert_eq!(to_logical(801, 600, 2.0, Bound::Min), (401.0, 300.0));
+        assert_eq!(to_logical(801, 600, 2.0, Bound::Max), (400.0, 300.0));
+        assert_eq!(to_logical(800, 600, 1.0, Bound::Min), (800.0, 600.0));
+        assert_eq!(to_logical(800, 600, 1.0, Bound::Max), (800.0, 600.0));
+        assert_eq!(to_logical(0, 0, 1.5, Bound::Min), (0.0, 0.0));
+    }
+
+    #[test]
+    fn size_constraint_to_logical_is_the_closest_size() {
+        for &scale_factor in &[1.0, 1.25, 1.5, 1.75, 2.0, 3.0] {
+            for pixels in 1..4000 {
+                let (min, _) = to_logical(pixels, pixels, scale_factor, Bound::Min);
+                assert!(to_pixels(min, scale_factor) >= pixels);
+                assert!(to_pixels(min - 1.0, scale_factor) < pixels);
+
+                let (max, _) = to_logical(pixels, pixels, scale_factor, Bound::Max);
+                assert!(to_pixels(max, scale_factor) <= pixels);
+                assert!(to_pixels(max + 1.0, scale_factor) > pixels);
+            }
+        }
+    }
+
+    fn rect(x: CGFloat, y: CGFloat, width: CGFloat, height: CGFloat) -> NSRect {
+        NSRect::new(NSPoint::new(x, y), NSSize::new(width, height))
+    }
+
+    #[test]
+    fn insets_of_the_safe_area() {
+        let bounds = rect(0.0, 0.0, 800.0, 600.0);
+        assert_eq!(insets_of(bounds, bounds, 2.0), SafeAreaInsets::default());
+        // A title bar of 28 points over a full-size content view.
+        let insets = SafeAreaInsets {
+            top: 56,
+            ..Default::default()
+        };
+        assert_eq!(insets_of(bounds, rect(0.0, 0.0, 800.0, 572.0), 2.0), insets);
+        let insets = SafeAreaInsets {
+            top: 42,
+            ..Default::default()
+        };
+        assert_eq!(insets_of(bounds, rect(0.0, 0.0, 800.0, 572.0), 1.5), insets);
+        // Insets on every side.
+        let insets = SafeAreaInsets {
+            top: 30,
+            left: 10,
+            bottom: 20,
+            right: 40,
+        };
+        assert_eq!(
+            insets_of(bounds, rect(10.0, 20.0, 750.0, 550.0), 1.0),
+            insets
+        );
+    }
+
+    #[test]
+    fn insets_of_a_safe_area_larger_than_the_bounds() {
+        let bounds = rect(0.0, 0.0, 800.0, 600.0);
+        let safe_rect = rect(-10.0, -10.0, 820.0, 620.0);
+        assert_eq!(insets_of(bounds, safe_rect, 2.0), SafeAreaInsets::default());
+    }
+
+    #[test]
+    fn inner_position_matches_the_outer_position_without_decorations() {
+        for &scale_factor in &[1.0, 1.25, 1.5, 1.75, 2.0] {
+            for &(x, y) in &[(0.0, 0.0), (100.3, 50.7), (-833.5, 21.5), (1279.9, -0.5)] {
+                let top_left = LogicalPosition::new(x, y);
+                assert_eq!(
+                    inner_position_from_outer(top_left, top_left, scale_factor),
+                    top_left.to_physical::<i32>(scale_factor)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn inner_position_is_offset_by_the_titlebar() {
+        let frame_top_left = LogicalPosition::new(100.5, 200.0);
+        let content_top_left = LogicalPosition::new(100.5, 228.0);
+        assert_eq!(
+            inner_position_from_outer(frame_top_left, content_top_left, 2.0),
+            PhysicalPosition::new(201, 456)
+        );
+        // The outer position rounds to (151, 300), and the titlebar is 42 pixels.
+        assert_eq!(
+            inner_position_from_outer(frame_top_left, content_top_left, 1.5),
+            PhysicalPosition::new(151, 342)
+        );
+    }
+
+    #[test]
+    fn appearance_name_to_theme_of_the_system_appearances() {
+        for name in [
+            "NSAppearanceNameDarkAqua",
+            "NSAppearanceNameVibrantDark",
+            "NSAppearanceNameAccessibilityHighContrastDarkAqua",
+            "NSAppearanceNameAccessibilityHighContrastVibrantDark",
+        ] {
+            assert_eq!(appearance_name_to_theme(name), Theme::Dark);
+        }
+        for name in [
+            "NSAppearanceNameAqua",
+            "NSAppearanceNameVibrantLight",
+            "NSAppearanceNameAccessibilityHighContrastAqua",
+            "NSAppearanceNameAccessibilityHighContrastVibrantLight",
+        ] {
+            assert_eq!(appearance_name_to_theme(name), Theme::Light);
+        }
+    }
+
+    #[test]
+    fn min_size_within_the_max_size() {
+        let within = |(width, height): (CGFloat, CGFloat)| {
+            let size = min_size_within(NSSize::new(width, height), NSSize::new(800.0, 600.0));
+            (size.width, size.height)
+        };
+        assert_eq!(within((400.0, 300.0)), (400.0, 300.0));
+        assert_eq!(within((1000.0, 300.0)), (800.0, 300.0));
+        assert_eq!(within((400.0, 700.0)), (400.0, 600.0));
+        assert_eq!(within((1000.0, 700.0)), (800.0, 600.0));
+    }
+
+    #[test]
+    fn clamp_content_size_to_the_constraints() {
+        let clamp = |(width, height): (CGFloat, CGFloat), min_size, max_size| {
+            let size = clamp_content_size(NSSize::new(width, height), min_size, max_size);
+            (size.width, size.height)
+        };
+        let min_size = NSSize::new(200.0, 100.0);
+        let max_size = NSSize::new(800.0, 600.0);
+        assert_eq!(clamp((400.0, 300.0), min_size, max_size), (400.0, 300.0));
+        assert_eq!(clamp((100.0, 50.0), min_size, max_size), (200.0, 100.0));
+        assert_eq!(clamp((1000.0, 700.0), min_size, max_size), (800.0, 600.0));
+        assert_eq!(clamp((100.0, 700.0), min_size, max_size), (200.0, 600.0));
+        // The default constraints leave the size alone.
+        let unconstrained = NSSize::new(f32::MAX as CGFloat, f32::MAX as CGFloat);
+        let none = NSSize::new(0.0, 0.0);
+        assert_eq!(clamp((1.0, 5000.0), none, unconstrained), (1.0, 5000.0));
+    }
+
+    #[test]
+    fn constrained_content_rect_keeps_the_top_left_corner() {
+        let min_size = NSSize::new(200.0, 100.0);
+        let max_size = NSSize::new(800.0, 600.0);
+        let constrained = |content_rect| {
+            constrained_content_rect(content_rect, min_size, max_size).map(|rect: NSRect| {
+                (
+                    rect.origin.x,
+                    rect.origin.y,
+                    rect.size.width,
+                    rect.size.height,
+                )
+            })
+        };
+        assert_eq!(constrained(rect(10.0, 20.0, 400.0, 300.0)), None);
+        assert_eq!(constrained(rect(10.0, 20.0, 200.0, 600.0)), None);
+        // The top edge is at 20 + 700 = 720 before and after.
+        assert_eq!(
+            constrained(rect(10.0, 20.0, 1000.0, 700.0)),
+            Some((10.0, 120.0, 800.0, 600.0))
+        );
+        // The top edge is at 20 + 50 = 70 before and after.
+        assert_eq!(
+            constrained(rect(10.0, 20.0, 100.0, 50.0)),
+            Some((10.0, -30.0, 200.0, 100.0))
+        );
+    }
+
+    #[test]
+    fn size_constraint_to_logical_keeps_logical_sizes() {
+        let size = LogicalSize::new(640.5, 480.25);
+        for &bound in &[Bound::Min, Bound::Max] {
+            assert_eq!(size_constraint_to_logical(size.into(), 1.5, bound), size);
+        }
+    }
+
+    #[test]
+    fn resize_increments_round_trip() {
+        for &(width, height, scale_factor) in &[(1, 1, 1.0), (3, 6, 1.5), (10, 4, 2.0)] {
+            let increments = PhysicalSize::new(width, height);
+            let logical = resize_increments_to_logical(increments.into(), scale_factor);
+            assert_eq!(logical.to_physical::<u32>(scale_factor), increments);
+        }
+    }
+
+    #[test]
+    fn resize_increments_round_trip_through_the_shared_state() {
+        let mut shared_state = SharedState::default();
+        assert_eq!(shared_state.resize_increments(2.0), None);
+
+        // Sub-point increments, as with `with_resize_increments` or
+        // `set_resize_increments`, which both go through the shared state.
+        let size = shared_state.set_resize_increments(Some(PhysicalSize::new(1, 1).into()), 2.0);
+        assert_eq!((size.width, size.height), (1.0, 1.0));
+        assert_eq!(
+            shared_state.resize_increments(2.0),
+            Some(PhysicalSize::new(2, 2))
+        );
+
+        let size = shared_state.set_resize_increments(Some(PhysicalSize::new(3, 6).into()), 1.5);
+        assert_eq!((size.width, size.height), (2.0, 4.0));
+        assert_eq!(
+            shared_state.resize_increments(1.5),
+            Some(PhysicalSize::new(3, 6))
+        );
+
+        let size = shared_state.set_resize_increments(None, 1.5);
+        assert_eq!((size.width, size.height), (1.0, 1.0));
+        assert_eq!(shared_state.resize_increments(1.5), None);
+    }
+
+    #[test]
+    fn resize_increments_are_at_least_a_point() {
+        let logical = resize_increments_to_logical(PhysicalSize::new(1, 3).into(), 2.0);
+        assert_eq!(logical, LogicalSize::new(1.0, 1.5));
+        assert_eq!(
+            logical.to_physical::<u32>(2.0),
+            PhysicalSize::<u32>::new(2, 3)
+        );
+    }
+
+    #[test]
+    fn cursor_is_hidden_by_a_lock_unless_visible_while_locked() {
+        assert!(cursor_hidden_by_grab(true, false));
+        assert!(!cursor_hidden_by_grab(true, true));
+        assert!(!cursor_hidden_by_grab(false, false));
+        assert!(!cursor_hidden_by_grab(false, true));
+    }
+
+    #[test]
+    fn cursor_is_locked_again_after_fullscreen_only_in_the_key_window() {
+        assert!(should_relock_cursor(true, true));
+        assert!(!should_relock_cursor(true, false));
+        assert!(!should_relock_cursor(false, true));
+        assert!(!should_relock_cursor(false, false));
+    }
+
+    #[test]
+    fn outer_size_adds_the_titlebar_to_the_inner_size() {
+        // A titled window with a 28 points title bar.
+        let frame = rect(100.0, 100.0, 800.0, 628.0);
+        let content_rect = rect(100.0, 100.0, 800.0, 600.0);
+        let titlebar_height = titlebar_height_of(frame, content_rect);
+        assert_eq!(titlebar_height, 28.0);
+
+        for &scale_factor in &[1.0, 1.5, 2.0] {
+            let geometry = Geometry::new(content_rect.size, frame.size, scale_factor);
+            let inner_size = geometry.inner_size.to_physical::<f64>(scale_factor);
+            let outer_size = geometry.outer_size.to_physical::<f64>(scale_factor);
+            assert_eq!(outer_size.width, inner_size.width);
+            assert_eq!(
+                outer_size.height - inner_size.height,
+                titlebar_height * scale_factor
+            );
+        }
+    }
+
+    #[test]
+    fn outer_size_is_the_inner_size_with_a_fullsize_content_view() {
+        // The content view covers the whole frame, but the layout rect still
+        // excludes the title bar.
+        let frame = rect(100.0, 100.0, 800.0, 628.0);
+        let layout_rect = rect(100.0, 100.0, 800.0, 600.0);
+        assert_eq!(titlebar_height_of(frame, layout_rect), 28.0);
+
+        let geometry = Geometry::new(frame.size, frame.size, 2.0);
+        assert_eq!(
+            geometry.outer_size.to_physical::<u32>(2.0),
+            geometry.inner_size.to_physical::<u32>(2.0)
+        );
+    }
+
+    #[test]
+    fn fullscreen_exit_applies_the_constraints_last() {
+        use FullscreenExitStep::*;
+        assert_eq!(
+            fullscreen_exit_steps(false),
+            [StyleMask, Frame, Maximized, SizeConstraints]
+        );
+        assert_eq!(
+            fullscreen_exit_steps(true),
+            [StyleMask, Maximized, SizeConstraints]
+        );
+    }
+
+    #[test]
+    fn constraints_changed_while_fullscreen_apply_to_the_restored_frame() {
+        // The frame saved when entering fullscreen, restored on exit, then
+        // constrained to a maximum set while the window was fullscreen.
+        let saved_frame = rect(100.0, 200.0, 800.0, 600.0);
+        let max_size = NSSize::new(400.0, 300.0);
+        let constrained =
+            constrained_content_rect(saved_frame, NSSize::new(0.0, 0.0), max_size).unwrap();
+        let NSRect { origin, size } = constrained;
+        assert_eq!(
+            (origin.x, origin.y, size.width, size.height),
+            (100.0, 500.0, 400.0, 300.0)
+        );
+
+        // Toggling fullscreen again without changing the constraints leaves
+        // the frame alone.
+        assert!(constrained_content_rect(constrained, NSSize::new(0.0, 0.0), max_size).is_none());
+    }
+
+    #[test]
+    fn maximizing_a_new_window_saves_its_initial_frame() {
+        // Creating a window with `with_maximized(true)` maximizes it before it's
+        // shown, so the state is already updated when `makeKeyAndOrderFront:`
+        // is called.
+        let mut shared_state = SharedState::default();
+        let initial_frame = rect(10.0, 20.0, 640.0, 480.0);
+        assert!(shared_state.update_maximized(true, false, initial_frame));
+        assert!(shared_state.maximized);
+        let NSRect { origin, size } = shared_state.saved_standard_frame();
+        assert_eq!(
+            (origin.x, origin.y, size.width, size.height),
+            (10.0, 20.0, 640.0, 480.0)
+        );
+
+        // Maximizing an already zoomed window keeps the saved frame.
+        assert!(shared_state.update_maximized(true, true, rect(0.0, 0.0, 1440.0, 900.0)));
+        assert_eq!(shared_state.saved_standard_frame().size.width, 640.0);
+    }
+
+    #[test]
+    fn maximizing_a_fullscreen_window_is_deferred() {
+        let mut shared_state = SharedState {
+            fullscreen: Some(Fullscreen::Borderless(None)),
+            ..Default::default()
+        };
+        assert!(!shared_state.update_maximized(true, false, rect(10.0, 20.0, 640.0, 480.0)));
+        assert!(shared_state.maximized);
+    }
+
+    #[test]
+    fn borderless_windows_are_made_minimizable_temporarily() {
+        let borderless = NSWindowStyleMask::NSBorderlessWindowMask;
+        assert_eq!(
+            minimizable_mask(borderless),
+            Some(NSWindowStyleMask::NSMiniaturizableWindowMask)
+        );
+
+        // A window without a minimize button keeps its other styles.
+        let titled =
+            NSWindowStyleMask::NSTitledWindowMask | NSWindowStyleMask::NSResizableWindowMask;
+        assert_eq!(
+            minimizable_mask(titled),
+            Some(titled | NSWindowStyleMask::NSMiniaturizableWindowMask)
+        );
+
+        let minimizable = titled | NSWindowStyleMask::NSMiniaturizableWindowMask;
+        assert_eq!(minimizable_mask(minimizable), None);
     }
 }
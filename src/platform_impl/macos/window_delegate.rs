@@ -1,4 +1,5 @@
 use std::{
+    cell::RefCell,
     f64,
     os::raw::c_void,
     sync::{Arc, Weak},
@@ -7,6 +8,7 @@ use std::{
 use cocoa::{
     appkit::{self, NSApplicationPresentationOptions, NSView, NSWindow, NSWindowOcclusionState},
     base::{id, nil},
+    foundation::NSString,
 };
 use objc2::foundation::{NSObject, NSUInteger};
 use objc2::rc::autoreleasepool;
@@ -20,7 +22,7 @@ use crate::{
         app_state::AppState,
         event::{EventProxy, EventWrapper},
         util::{self, IdRef},
-        view::ViewState,
+        view::{self, ViewState},
         window::{get_window_id, UnownedWindow},
     },
     window::{Fullscreen, WindowId},
@@ -44,6 +46,9 @@ struct WindowDelegateState {
 
     // Used to prevent redundant events.
     previous_scale_factor: f64,
+
+    // Whether the user is moving the window, between `MoveStarted` and `MoveEnded`.
+    user_moving: bool,
 }
 
 impl WindowDelegateState {
@@ -56,6 +61,7 @@ impl WindowDelegateState {
             initial_fullscreen,
             previous_position: None,
             previous_scale_factor: scale_factor,
+            user_moving: false,
         };
 
         if scale_factor != 1.0 {
@@ -97,8 +103,7 @@ impl WindowDelegateState {
 
     fn emit_move_event(&mut self) {
         let rect = unsafe { NSWindow::frame(*self.ns_window) };
-        let x = rect.origin.x as f64;
-        let y = util::bottom_left_to_top_left(rect);
+        let LogicalPosition { x, y } = util::top_left(rect);
         let moved = self.previous_position != Some((x, y));
         if moved {
             self.previous_position = Some((x, y));
@@ -108,8 +113,20 @@ impl WindowDelegateState {
         }
     }
 
+    fn end_user_move(&mut self) {
+        if !self.user_moving {
+            return;
+        }
+        self.user_moving = false;
+        // The window may have been closed in the meantime.
+        if self.with_window(|_| ()).is_some() {
+            self.emit_move_event();
+            self.emit_event(WindowEvent::MoveEnded);
+        }
+    }
+
     fn get_scale_factor(&self) -> f64 {
-        (unsafe { NSWindow::backingScaleFactor(*self.ns_window) }) as f64
+        util::scale_factor(unsafe { NSWindow::backingScaleFactor(*self.ns_window) })
     }
 
     fn view_size(&self) -> LogicalSize<f64> {
@@ -118,6 +135,22 @@ impl WindowDelegateState {
     }
 }
 
+thread_local! {
+    // The delegates of the windows that the user is moving, between `MoveStarted`
+    // and `MoveEnded`. Only accessed from the main thread, like `sendEvent:`.
+    static MOVING_DELEGATES: RefCell<Vec<IdRef>> = RefCell::new(Vec::new());
+}
+
+/// Ends the moves of all windows that the user is moving.
+///
+/// Called from `sendEvent:` when the left mouse button is released.
+pub(super) fn end_user_moves() {
+    for delegate in MOVING_DELEGATES.with(|delegates| delegates.take()) {
+        let delegate = unsafe { &*(*delegate as *const WinitWindowDelegate) };
+        delegate.with_state(|state| state.end_user_move());
+    }
+}
+
 pub fn new_delegate(window: &Arc<UnownedWindow>, initial_fullscreen: bool) -> IdRef {
     let state = WindowDelegateState::new(window, initial_fullscreen);
     unsafe {
@@ -151,8 +184,21 @@ declare_class!(
             let this: Option<&mut Self> = unsafe { msg_send![self, init] };
             this.map(|this| {
                 *this.state = state;
-                this.with_state(|state| {
-                    let _: () = unsafe { msg_send![*state.ns_window, setDelegate: &*this] };
+                this.with_state(|state| unsafe {
+                    let _: () = msg_send![*state.ns_window, setDelegate: &*this];
+                    // The view is also resized on its own, e.g. when the titlebar is
+                    // hidden, which `windowDidResize:` doesn't report.
+                    let notification_center: &Object =
+                        msg_send![class!(NSNotificationCenter), defaultCenter];
+                    let frame_did_change_notification_name =
+                        IdRef::new(NSString::alloc(nil).init_str("NSViewFrameDidChangeNotification"));
+                    let _: () = msg_send![
+                        notification_center,
+                        addObserver: &*this
+                        selector: sel!(viewFrameDidChange:)
+                        name: *frame_did_change_notification_name
+                        object: *state.ns_view
+                    ];
                 });
                 this
             })
@@ -177,6 +223,9 @@ declare_class!(
                     // Since El Capitan, we need to be careful that delegate methods can't
                     // be called after the window closes.
                     let _: () = msg_send![*state.ns_window, setDelegate: nil];
+                    let notification_center: &Object =
+                        msg_send![class!(NSNotificationCenter), defaultCenter];
+                    let _: () = msg_send![notification_center, removeObserver: self];
                 });
                 state.emit_event(WindowEvent::Destroyed);
             });
@@ -186,17 +235,65 @@ declare_class!(
         fn window_did_resize(&self, _: id) {
             trace_scope!("windowDidResize:");
             self.with_state(|state| {
+                state.with_window(|window| window.update_geometry());
                 // NOTE: WindowEvent::Resized is reported in frameDidChange.
                 state.emit_move_event();
             });
         }
 
+        #[sel(windowWillMove:)]
+        fn window_will_move(&self, _: id) {
+            trace_scope!("windowWillMove:");
+            let mut started = false;
+            self.with_state(|state| {
+                if !state.user_moving {
+                    state.user_moving = true;
+                    state.emit_event(WindowEvent::MoveStarted);
+                    started = true;
+                }
+            });
+            if started {
+                let delegate = IdRef::retain(self as *const Self as id);
+                MOVING_DELEGATES.with(|delegates| delegates.borrow_mut().push(delegate));
+            }
+        }
+
+        #[sel(viewFrameDidChange:)]
+        fn view_frame_did_change(&self, _: id) {
+            trace_scope!("viewFrameDidChange:");
+            self.with_state(|state| {
+                state.with_window(|window| window.update_geometry());
+            });
+        }
+
         // This won't be triggered if the move was part of a resize.
         #[sel(windowDidMove:)]
         fn window_did_move(&self, _: id) {
             trace_scope!("windowDidMove:");
+            // The mouse up that ends a move isn't always sent to the application,
+            // e.g. when the window server moves the window by itself. The move is
+            // then over once the window moved with the mouse button released. This
+            // also ends the moves that don't involve the mouse, e.g. by a window
+            // manager, right away.
+            let pressed_mouse_buttons: NSUInteger =
+                unsafe { msg_send![class!(NSEvent), pressedMouseButtons] };
             self.with_state(|state| {
-                state.emit_move_event();
+                if state.user_moving && pressed_mouse_buttons & 1 == 0 {
+                    state.end_user_move();
+                } else {
+                    state.emit_move_event();
+                }
+            });
+        }
+
+        #[sel(windowDidChangeScreen:)]
+        fn window_did_change_screen(&self, _: id) {
+            trace_scope!("windowDidChangeScreen:");
+            self.with_state(|state| {
+                state.with_window(|window| {
+                    window.update_display_link_display();
+                    window.update_monitor_constraint();
+                });
             });
         }
 
@@ -204,6 +301,10 @@ declare_class!(
         fn window_did_change_backing_properties(&self, _: id) {
             trace_scope!("windowDidChangeBackingProperties:");
             self.with_state(|state| {
+                state.with_window(|window| {
+                    window.update_geometry();
+                    window.restore_surface_scale();
+                });
                 state.emit_static_scale_factor_changed_event();
             });
         }
@@ -214,6 +315,9 @@ declare_class!(
             self.with_state(|state| {
                 // TODO: center the cursor if the window had mouse grab when it
                 // lost focus
+                state.with_window(|window| window.set_cursor_grab_suspended(false));
+                unsafe { view::focus_gained_ime(*state.ns_view) };
+                unsafe { view::update_cursor_hidden(*state.ns_view) };
                 state.emit_event(WindowEvent::Focused(true));
             });
         }
@@ -245,6 +349,16 @@ declare_class!(
                     state.emit_event(WindowEvent::ModifiersChanged(view_state.modifiers));
                 }
 
+                // Release a locked cursor so that it isn't trapped while
+                // another window or application is focused.
+                state.with_window(|window| window.set_cursor_grab_suspended(true));
+
+                // Don't leave a composition dangling while another window is focused.
+                unsafe { view::focus_lost_ime(*state.ns_view) };
+
+                // Don't keep the cursor hidden over other windows.
+                unsafe { view::update_cursor_hidden(*state.ns_view) };
+
                 state.emit_event(WindowEvent::Focused(false));
             });
         }
@@ -328,14 +442,23 @@ declare_class!(
         }
 
         /// Invoked when before enter fullscreen
-        #[sel(windowWillEnterFullscreen:)]
+        #[sel(windowWillEnterFullScreen:)]
         fn window_will_enter_fullscreen(&self, _: id) {
-            trace_scope!("windowWillEnterFullscreen:");
+            trace_scope!("windowWillEnterFullScreen:");
 
             self.with_state(|state| {
                 state.with_window(|window| {
                     let mut shared_state = window.lock_shared_state("window_will_enter_fullscreen");
                     shared_state.maximized = window.is_zoomed();
+                    // The frame is restored once fullscreen is exited, as AppKit
+                    // may otherwise restore one that was changed in the meantime,
+                    // see `restore_state_from_fullscreen`. Like in `set_maximized`,
+                    // the standard frame is only saved if the window isn't zoomed,
+                    // as it's the one a zoomed window returns to.
+                    if !shared_state.maximized {
+                        shared_state.standard_frame =
+                            Some(unsafe { NSWindow::frame(*window.ns_window) });
+                    }
                     let fullscreen = shared_state.fullscreen.as_ref();
                     match fullscreen {
                         // Exclusive mode sets the state in `set_fullscreen` as the user
@@ -404,9 +527,9 @@ declare_class!(
         }
 
         /// Invoked when entered fullscreen
-        #[sel(windowDidEnterFullscreen:)]
+        #[sel(windowDidEnterFullScreen:)]
         fn window_did_enter_fullscreen(&self, _: id) {
-            trace_scope!("windowDidEnterFullscreen:");
+            trace_scope!("windowDidEnterFullScreen:");
             self.with_state(|state| {
                 state.initial_fullscreen = false;
                 state.with_window(|window| {
@@ -414,6 +537,7 @@ declare_class!(
                     shared_state.in_fullscreen_transition = false;
                     let target_fullscreen = shared_state.target_fullscreen.take();
                     drop(shared_state);
+                    window.reapply_cursor_state();
                     if let Some(target_fullscreen) = target_fullscreen {
                         window.set_fullscreen(target_fullscreen);
                     }
@@ -422,9 +546,9 @@ declare_class!(
         }
 
         /// Invoked when exited fullscreen
-        #[sel(windowDidExitFullscreen:)]
+        #[sel(windowDidExitFullScreen:)]
         fn window_did_exit_fullscreen(&self, _: id) {
-            trace_scope!("windowDidExitFullscreen:");
+            trace_scope!("windowDidExitFullScreen:");
 
             self.with_state(|state| {
                 state.with_window(|window| {
@@ -433,6 +557,7 @@ declare_class!(
                     shared_state.in_fullscreen_transition = false;
                     let target_fullscreen = shared_state.target_fullscreen.take();
                     drop(shared_state);
+                    window.reapply_cursor_state();
                     if let Some(target_fullscreen) = target_fullscreen {
                         window.set_fullscreen(target_fullscreen);
                     }
@@ -456,9 +581,9 @@ declare_class!(
         /// due to being in the midst of handling some other animation or user gesture.
         /// This method indicates that there was an error, and you should clean up any
         /// work you may have done to prepare to enter full-screen mode.
-        #[sel(windowDidFailToEnterFullscreen:)]
+        #[sel(windowDidFailToEnterFullScreen:)]
         fn window_did_fail_to_enter_fullscreen(&self, _: id) {
-            trace_scope!("windowDidFailToEnterFullscreen:");
+            trace_scope!("windowDidFailToEnterFullScreen:");
             self.with_state(|state| {
                 state.with_window(|window| {
                     let mut shared_state =
@@ -486,12 +611,12 @@ declare_class!(
             trace_scope!("windowDidChangeOcclusionState:");
             unsafe {
                 self.with_state(|state| {
-                    state.emit_event(WindowEvent::Occluded(
-                        !state
-                            .ns_window
-                            .occlusionState()
-                            .contains(NSWindowOcclusionState::NSWindowOcclusionStateVisible),
-                    ))
+                    let occluded = !state
+                        .ns_window
+                        .occlusionState()
+                        .contains(NSWindowOcclusionState::NSWindowOcclusionStateVisible);
+                    state.with_window(|window| window.set_occluded(occluded));
+                    state.emit_event(WindowEvent::Occluded(occluded))
                 });
             }
         }
@@ -11,7 +11,7 @@ use std::rc::Rc;
 
 use wasm_bindgen::{closure::Closure, JsCast};
 use web_sys::{
-    AddEventListenerOptions, Event, FocusEvent, HtmlCanvasElement, KeyboardEvent,
+    AddEventListenerOptions, Element, Event, FocusEvent, HtmlCanvasElement, KeyboardEvent,
     MediaQueryListEvent, MouseEvent, WheelEvent,
 };
 
@@ -95,6 +95,14 @@ impl Canvas {
         })
     }
 
+    pub fn is_cursor_locked(&self) -> bool {
+        let canvas: &Element = self.raw();
+        web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.pointer_lock_element())
+            .map_or(false, |element| element == *canvas)
+    }
+
     pub fn set_cursor_lock(&self, lock: bool) -> Result<(), RootOE> {
         if lock {
             self.raw().request_pointer_lock();
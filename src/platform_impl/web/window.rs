@@ -113,6 +113,10 @@ impl Window {
         self.outer_position()
     }
 
+    pub fn center_on_monitor(&self, _monitor: &RootMH) {
+        // Not supported
+    }
+
     pub fn set_outer_position(&self, position: Position) {
         let position = position.to_logical::<f64>(self.scale_factor());
 
@@ -133,6 +137,16 @@ impl Window {
         self.inner_size()
     }
 
+    #[inline]
+    pub fn inner_size_f64(&self) -> PhysicalSize<f64> {
+        self.inner_size().cast()
+    }
+
+    #[inline]
+    pub fn outer_size_f64(&self) -> PhysicalSize<f64> {
+        self.outer_size().cast()
+    }
+
     #[inline]
     pub fn set_inner_size(&self, size: Size) {
         let old_size = self.inner_size();
@@ -228,7 +242,7 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<CursorGrabMode, ExternalError> {
         let lock = match mode {
             CursorGrabMode::None => false,
             CursorGrabMode::Locked => true,
@@ -237,9 +251,17 @@ impl Window {
             }
         };
 
-        self.canvas
-            .borrow()
+        let canvas = self.canvas.borrow();
+        // The user can exit the pointer lock at any time, so the previous mode
+        // is read from the document rather than tracked.
+        let previous_mode = if canvas.is_cursor_locked() {
+            CursorGrabMode::Locked
+        } else {
+            CursorGrabMode::None
+        };
+        canvas
             .set_cursor_lock(lock)
+            .map(|()| previous_mode)
             .map_err(ExternalError::Os)
     }
 
@@ -264,6 +286,14 @@ impl Window {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    #[inline]
+    pub fn set_content_protected(&self, _protected: bool) {}
+
+    #[inline]
+    pub fn is_content_protected(&self) -> bool {
+        false
+    }
+
     #[inline]
     pub fn set_minimized(&self, _minimized: bool) {
         // Intentionally a no-op, as canvases cannot be 'minimized'
@@ -328,8 +358,9 @@ impl Window {
     }
 
     #[inline]
-    pub fn focus_window(&self) {
+    pub fn focus_window(&self) -> bool {
         // Currently a no-op as it does not seem there is good support for this on web
+        false
     }
 
     #[inline]
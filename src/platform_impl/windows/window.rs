@@ -149,6 +149,12 @@ impl Window {
         Ok(PhysicalPosition::new(position.x as i32, position.y as i32))
     }
 
+    #[inline]
+    pub fn center_on_monitor(&self, monitor: &RootMonitorHandle) {
+        let position = crate::window::centered_on_monitor(monitor, self.outer_size());
+        self.set_outer_position(position.into());
+    }
+
     #[inline]
     pub fn set_outer_position(&self, position: Position) {
         let (x, y): (i32, i32) = position.to_physical::<i32>(self.scale_factor()).into();
@@ -201,6 +207,17 @@ impl Window {
             .unwrap()
     }
 
+    // Window sizes are whole pixels on Windows.
+    #[inline]
+    pub fn inner_size_f64(&self) -> PhysicalSize<f64> {
+        self.inner_size().cast()
+    }
+
+    #[inline]
+    pub fn outer_size_f64(&self) -> PhysicalSize<f64> {
+        self.outer_size().cast()
+    }
+
     #[inline]
     pub fn set_inner_size(&self, size: Size) {
         let scale_factor = self.scale_factor();
@@ -296,7 +313,7 @@ impl Window {
     }
 
     #[inline]
-    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<CursorGrabMode, ExternalError> {
         let confine = match mode {
             CursorGrabMode::None => false,
             CursorGrabMode::Confined => true,
@@ -311,11 +328,20 @@ impl Window {
 
         self.thread_executor.execute_in_thread(move || {
             let _ = &window;
+            let mut window_state = window_state.lock().unwrap();
+            let previous_mode = if window_state
+                .mouse
+                .cursor_flags()
+                .contains(CursorFlags::GRABBED)
+            {
+                CursorGrabMode::Confined
+            } else {
+                CursorGrabMode::None
+            };
             let result = window_state
-                .lock()
-                .unwrap()
                 .mouse
                 .set_cursor_flags(window.0, |f| f.set(CursorFlags::GRABBED, confine))
+                .map(|()| previous_mode)
                 .map_err(|e| ExternalError::Os(os_error!(e)));
             let _ = tx.send(result);
         });
@@ -400,6 +426,14 @@ impl Window {
         Ok(())
     }
 
+    #[inline]
+    pub fn set_content_protected(&self, _protected: bool) {}
+
+    #[inline]
+    pub fn is_content_protected(&self) -> bool {
+        false
+    }
+
     #[inline]
     pub fn id(&self) -> WindowId {
         WindowId(self.hwnd())
@@ -722,7 +756,7 @@ impl Window {
     }
 
     #[inline]
-    pub fn focus_window(&self) {
+    pub fn focus_window(&self) -> bool {
         let window = self.window.clone();
         let window_flags = self.window_state_lock().window_flags();
 
@@ -733,6 +767,7 @@ impl Window {
         if is_visible && !is_minimized && !is_foreground {
             unsafe { force_window_active(window.0) };
         }
+        is_visible && !is_minimized
     }
 }
 
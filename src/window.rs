@@ -433,6 +433,11 @@ impl Window {
     ///
     /// - **iOS:** Can only be called on the main thread.
     /// - **Android:** Subsequent calls after `MainEventsCleared` are not handled.
+    /// - **macOS:** Deferred while the window is occluded, until it's revealed. See
+    ///   `WindowExtMacOS::set_redraw_while_occluded`. Calling this while handling
+    ///   `RedrawRequested` delivers another one on the next iteration of the event loop, even with
+    ///   [`ControlFlow::Wait`](crate::event_loop::ControlFlow::Wait), which is enough to animate
+    ///   continuously.
     ///
     /// [`Event::RedrawRequested`]: crate::event::Event::RedrawRequested
     /// [`Event::MainEventsCleared`]: crate::event::Event::MainEventsCleared
@@ -513,6 +518,51 @@ impl Window {
         self.window.set_outer_position(position.into())
     }
 
+    /// Modifies the position of the window like [`Window::set_outer_position`], but snaps the
+    /// window flush to the edges of the monitors that are within `threshold` physical pixels of
+    /// its edges at `position`.
+    ///
+    /// Each axis is snapped on its own, to the closest edge, by aligning the left, top, right or
+    /// bottom edge of the window with the same edge of a monitor that the window overlaps or is
+    /// within `threshold` of. At the boundary between two adjacent monitors, the window is thus
+    /// snapped onto the monitor that it's already mostly on, and doesn't straddle the boundary by
+    /// less than `threshold`.
+    ///
+    /// The edges are those of the whole monitor, see [`MonitorHandle::position`] and
+    /// [`MonitorHandle::size`], including the parts that may be covered by a taskbar or a menu
+    /// bar.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Can only be called on the main thread.
+    /// - **Web:** The position isn't snapped, as no monitors are known.
+    /// - **Android / Wayland:** Unsupported.
+    #[inline]
+    pub fn set_outer_position_snapped<P: Into<Position>>(&self, position: P, threshold: u32) {
+        let position = position.into().to_physical(self.scale_factor());
+        let monitors = self
+            .available_monitors()
+            .map(|monitor| (monitor.position(), monitor.size()));
+        let position = snapped_to_monitors(position, self.outer_size(), monitors, threshold);
+        self.window.set_outer_position(position.into())
+    }
+
+    /// Moves the window to the center of the given monitor, e.g. to open it on the monitor that
+    /// the cursor is on.
+    ///
+    /// A window that is larger than the monitor in a dimension is aligned with the monitor's left
+    /// or top edge instead, so that its title bar stays on the monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The window is centered within the part of the monitor that isn't covered by
+    ///   the menu bar and the Dock.
+    /// - **iOS / Android / Web / Wayland:** Unsupported.
+    #[inline]
+    pub fn center_on_monitor(&self, monitor: &MonitorHandle) {
+        self.window.center_on_monitor(monitor)
+    }
+
     /// Returns the physical size of the window's client area.
     ///
     /// The client area is the content of the window, excluding the title bar and borders.
@@ -529,6 +579,22 @@ impl Window {
         self.window.inner_size()
     }
 
+    /// Returns the physical size of the window's client area, without rounding it to whole pixels.
+    ///
+    /// At fractional scale factors, the size of the client area isn't necessarily a whole number of
+    /// pixels, and [`Window::inner_size`] rounds it. Use this instead when the exact size matters,
+    /// e.g. to compute a viewport that lines up with the backing store. Use
+    /// [`Window::inner_size`] to size textures and swapchains, which need whole pixels.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The exact size, which can be fractional.
+    /// - **Other platforms:** The same as [`Window::inner_size`], as sizes are whole pixels.
+    #[inline]
+    pub fn inner_size_f64(&self) -> PhysicalSize<f64> {
+        self.window.inner_size_f64()
+    }
+
     /// Modifies the inner size of the window.
     ///
     /// See [`Window::inner_size`] for more information about the values.
@@ -551,6 +617,10 @@ impl Window {
     ///
     /// - **iOS / Android:** Unsupported.
     /// - **Web:** Sets the size of the canvas element.
+    /// - **macOS:** The size is silently clamped to the minimum and maximum inner sizes. The
+    ///   resulting [`WindowEvent::Resized`] reports the size that was actually applied.
+    ///
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
     #[inline]
     pub fn set_inner_size<S: Into<Size>>(&self, size: S) {
         self.window.set_inner_size(size.into())
@@ -567,11 +637,27 @@ impl Window {
     ///   screen space coordinates.
     /// - **Web:** Returns the size of the canvas element. _Note: this returns the same value as
     ///   [`Window::inner_size`]._
+    /// - **macOS:** Windows have no borders: the width is the same as [`Window::inner_size`], and
+    ///   the height only adds the title bar, i.e. `WindowExtMacOS::titlebar_height`. With a
+    ///   full-size content view, which extends under the title bar, both sizes are the same.
     #[inline]
     pub fn outer_size(&self) -> PhysicalSize<u32> {
         self.window.outer_size()
     }
 
+    /// Returns the physical size of the entire window, without rounding it to whole pixels.
+    ///
+    /// See [`Window::inner_size_f64`] for when to use this over [`Window::outer_size`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The exact size, which can be fractional.
+    /// - **Other platforms:** The same as [`Window::outer_size`], as sizes are whole pixels.
+    #[inline]
+    pub fn outer_size_f64(&self) -> PhysicalSize<f64> {
+        self.window.outer_size_f64()
+    }
+
     /// Sets a minimum dimension size for the window.
     ///
     /// ```no_run
@@ -589,6 +675,9 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
+    /// - **macOS:** A minimum larger than the maximum is clamped down to the maximum, in each
+    ///   dimension. The inner size is kept at whole points, so a physical size that doesn't fall
+    ///   on whole points is rounded up to the closest size that does.
     /// - **iOS / Android / Web:** Unsupported.
     #[inline]
     pub fn set_min_inner_size<S: Into<Size>>(&self, min_size: Option<S>) {
@@ -612,6 +701,9 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
+    /// - **macOS:** A maximum smaller than the minimum lowers the minimum to match, in each
+    ///   dimension. The inner size is kept at whole points, so a physical size that doesn't fall
+    ///   on whole points is rounded down to the closest size that does.
     /// - **iOS / Android / Web:** Unsupported.
     #[inline]
     pub fn set_max_inner_size<S: Into<Size>>(&self, max_size: Option<S>) {
@@ -636,6 +728,8 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **macOS:** Increments are converted to logical size and then macOS rounds them to whole numbers.
+    ///   Increments smaller than a point are raised to one point, which is also what
+    ///   [`Window::resize_increments`] returns afterwards.
     /// - **Wayland / Windows:** Not implemented.
     /// - **iOS / Android / Web:** Unsupported.
     #[inline]
@@ -651,6 +745,8 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
+    /// - **macOS:** The entries of the window in the Window menu and the Dock menu are updated as
+    ///   well.
     /// - **iOS / Android:** Unsupported.
     #[inline]
     pub fn set_title(&self, title: &str) {
@@ -693,6 +789,8 @@ impl Window {
     ///
     /// This only has an effect on desktop platforms.
     ///
+    /// - **macOS:** The zoom button, which also enters fullscreen, is disabled while the window
+    ///   isn't resizable. [`Window::set_fullscreen`] still works.
     /// - **X11:** Due to a bug in XFCE, this has no effect on Xfwm.
     /// - **iOS / Android / Web:** Unsupported.
     ///
@@ -894,15 +992,20 @@ impl Window {
     /// Brings the window to the front and sets input focus. Has no effect if the window is
     /// already in focus, minimized, or not visible.
     ///
+    /// Returns `false` if the window is minimized or not visible, in which case nothing was
+    /// requested and the window should be restored with [`Window::set_minimized`] or shown with
+    /// [`Window::set_visible`] first. Returns `true` otherwise, including when the window is
+    /// already in focus. The system may still refuse to give focus to the window.
+    ///
     /// This method steals input focus from other applications. Do not use this method unless
     /// you are certain that's what the user wants. Focus stealing can cause an extremely disruptive
     /// user experience.
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Wayland:** Unsupported.
+    /// - **iOS / Android / Web / Wayland:** Unsupported, always returns `false`.
     #[inline]
-    pub fn focus_window(&self) {
+    pub fn focus_window(&self) -> bool {
         self.window.focus_window()
     }
 
@@ -960,8 +1063,36 @@ impl Window {
         self.window.set_cursor_position(position.into())
     }
 
+    /// Changes the position of the cursor in window coordinates, like
+    /// [`Window::set_cursor_position`], but keeps the cursor within the window's client area.
+    ///
+    /// Each coordinate is clamped between `0` and the last pixel of the [`Window::inner_size`],
+    /// so the cursor never ends up on the decorations or outside of the window, e.g. when
+    /// re-centering it while the window is being resized.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Wayland:** Always returns an [`ExternalError::NotSupported`].
+    #[inline]
+    pub fn set_cursor_position_clamped<P: Into<Position>>(
+        &self,
+        position: P,
+    ) -> Result<(), ExternalError> {
+        let scale_factor = self.scale_factor();
+        let position = position.into().to_physical::<f64>(scale_factor);
+        let size = self.inner_size();
+        let position = PhysicalPosition::new(
+            position.x.clamp(0.0, size.width.saturating_sub(1) as f64),
+            position.y.clamp(0.0, size.height.saturating_sub(1) as f64),
+        );
+        self.set_cursor_position(position.to_logical::<f64>(scale_factor))
+    }
+
     /// Set grabbing [mode]([`CursorGrabMode`]) on the cursor preventing it from leaving the window.
     ///
+    /// On success, returns the mode that was in effect before, so that it can be restored later,
+    /// e.g. to grab the cursor for the duration of a drag.
+    ///
     /// # Example
     ///
     /// First try confining the cursor, and if that fails, try locking it instead.
@@ -975,8 +1106,13 @@ impl Window {
     ///             .or_else(|_e| window.set_cursor_grab(CursorGrabMode::Locked))
     ///             .unwrap();
     /// ```
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The grab is suspended while the window is unfocused, and restored once it
+    ///   regains focus.
     #[inline]
-    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<CursorGrabMode, ExternalError> {
         self.window.set_cursor_grab(mode)
     }
 
@@ -1006,7 +1142,9 @@ impl Window {
     ///
     /// - **X11:** Un-grabs the cursor.
     /// - **Wayland:** Requires the cursor to be inside the window to be dragged.
-    /// - **macOS:** May prevent the button release event to be triggered.
+    /// - **macOS:** May prevent the button release event to be triggered. Works both when a
+    ///   mouse button was just pressed and while the mouse is moved with a button pressed, e.g.
+    ///   to only start the drag once the mouse moved past a threshold.
     /// - **iOS / Android / Web:** Always returns an [`ExternalError::NotSupported`].
     #[inline]
     pub fn drag_window(&self) -> Result<(), ExternalError> {
@@ -1025,6 +1163,33 @@ impl Window {
     pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), ExternalError> {
         self.window.set_cursor_hittest(hittest)
     }
+
+    /// Prevents the window contents from being captured by other applications, e.g. in
+    /// screenshots and screen recordings.
+    ///
+    /// The system doesn't notify applications when their contents are captured, or when a capture
+    /// was attempted, so there's no corresponding event.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Sets the window's sharing type to `NSWindowSharingNone`. The window shows up
+    ///   as blank in captures.
+    /// - **iOS / Android / Web / Windows / X11 / Wayland:** Unsupported.
+    #[inline]
+    pub fn set_content_protected(&self, protected: bool) {
+        self.window.set_content_protected(protected)
+    }
+
+    /// Returns whether the window contents are protected from being captured, as set by
+    /// [`Window::set_content_protected`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Windows / X11 / Wayland:** Always returns `false`.
+    #[inline]
+    pub fn is_content_protected(&self) -> bool {
+        self.window.is_content_protected()
+    }
 }
 
 /// Monitor info functions.
@@ -1067,6 +1232,7 @@ impl Window {
     /// ## Platform-specific
     ///
     /// **iOS:** Can only be called on the main thread.
+    /// **macOS:** The monitor with the menu bar.
     /// **Wayland:** Always returns `None`.
     ///
     /// [`EventLoopWindowTarget::primary_monitor`]: crate::event_loop::EventLoopWindowTarget::primary_monitor
@@ -1075,6 +1241,71 @@ impl Window {
         self.window.primary_monitor()
     }
 }
+
+/// Returns the position of the top-left corner of a window of `outer_size` centered on a monitor,
+/// see [`Window::center_on_monitor`].
+pub(crate) fn centered_on_monitor(
+    monitor: &MonitorHandle,
+    outer_size: PhysicalSize<u32>,
+) -> PhysicalPosition<i32> {
+    centered_in(monitor.position(), monitor.size(), outer_size)
+}
+
+fn centered_in(
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+    outer_size: PhysicalSize<u32>,
+) -> PhysicalPosition<i32> {
+    PhysicalPosition::new(
+        position.x + (size.width.saturating_sub(outer_size.width) / 2) as i32,
+        position.y + (size.height.saturating_sub(outer_size.height) / 2) as i32,
+    )
+}
+
+/// Returns the position of a window of `outer_size` at `position` once snapped to the monitors,
+/// given by their position and size, see [`Window::set_outer_position_snapped`].
+fn snapped_to_monitors(
+    position: PhysicalPosition<i32>,
+    outer_size: PhysicalSize<u32>,
+    monitors: impl Iterator<Item = (PhysicalPosition<i32>, PhysicalSize<u32>)>,
+    threshold: u32,
+) -> PhysicalPosition<i32> {
+    // Computed in `i64`, so that neither large sizes nor a large threshold overflow.
+    let (x, y) = (position.x as i64, position.y as i64);
+    let (width, height) = (outer_size.width as i64, outer_size.height as i64);
+    let threshold = threshold as i64;
+    // The distance and position of the closest snap on each axis.
+    let mut snap_x: Option<(i64, i64)> = None;
+    let mut snap_y: Option<(i64, i64)> = None;
+    let consider = |snap: &mut Option<(i64, i64)>, from: i64, to: i64| {
+        let distance = (to - from).abs();
+        if distance <= threshold && snap.map_or(true, |(closest, _)| distance < closest) {
+            *snap = Some((distance, to));
+        }
+    };
+    for (origin, size) in monitors {
+        let (origin_x, origin_y) = (origin.x as i64, origin.y as i64);
+        let (monitor_width, monitor_height) = (size.width as i64, size.height as i64);
+        // Skip the monitors that are farther than `threshold` from the window.
+        if x + width + threshold < origin_x
+            || origin_x + monitor_width + threshold < x
+            || y + height + threshold < origin_y
+            || origin_y + monitor_height + threshold < y
+        {
+            continue;
+        }
+        consider(&mut snap_x, x, origin_x);
+        consider(&mut snap_x, x, origin_x + monitor_width - width);
+        consider(&mut snap_y, y, origin_y);
+        consider(&mut snap_y, y, origin_y + monitor_height - height);
+    }
+    let clamp = |value: i64| value.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+    PhysicalPosition::new(
+        snap_x.map_or(position.x, |(_, x)| clamp(x)),
+        snap_y.map_or(position.y, |(_, y)| clamp(y)),
+    )
+}
+
 unsafe impl HasRawWindowHandle for Window {
     /// Returns a [`raw_window_handle::RawWindowHandle`] for the Window
     ///
@@ -1236,6 +1467,8 @@ pub enum CursorGrabMode {
     ///
     /// ## Platform-specific
     ///
+    /// - **macOS:** The cursor is hidden while it's locked, unless disabled with
+    ///   `WindowExtMacOS::set_cursor_visible_while_locked`.
     /// - **X11 / Windows:** Not implemented. Always returns [`ExternalError::NotSupported`] for now.
     /// - **iOS / Android:** Always returns an [`ExternalError::NotSupported`].
     Locked,
@@ -1258,12 +1491,22 @@ pub enum CursorIcon {
     /// Indicates text that may be selected or edited.
     Text,
     /// Program busy indicator.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The same animated arrow with a spinning wheel as [`CursorIcon::Progress`], as
+    ///   there's no system cursor for a program that can't be interacted with.
     Wait,
     /// Help indicator (often rendered as a "?")
     Help,
     /// Progress indicator. Shows that processing is being done. But in contrast
     /// with "Wait" the user may still interact with the program. Often rendered
     /// as a spinning beach ball, or an arrow with a watch or hourglass.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** An arrow with a spinning wheel, animated by the system. Falls back to
+    ///   [`CursorIcon::Default`] on versions of macOS that lack it.
     Progress,
 
     /// Cursor showing that something cannot be done.
@@ -1346,3 +1589,103 @@ impl Default for UserAttentionType {
         UserAttentionType::Informational
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONITOR: (PhysicalPosition<i32>, PhysicalSize<u32>) =
+        (PhysicalPosition::new(0, 0), PhysicalSize::new(1920, 1080));
+    const RIGHT_MONITOR: (PhysicalPosition<i32>, PhysicalSize<u32>) = (
+        PhysicalPosition::new(1920, 0),
+        PhysicalSize::new(1920, 1080),
+    );
+
+    fn snapped(
+        (x, y): (i32, i32),
+        (width, height): (u32, u32),
+        monitors: &[(PhysicalPosition<i32>, PhysicalSize<u32>)],
+        threshold: u32,
+    ) -> (i32, i32) {
+        let position = PhysicalPosition::new(x, y);
+        let outer_size = PhysicalSize::new(width, height);
+        snapped_to_monitors(position, outer_size, monitors.iter().copied(), threshold).into()
+    }
+
+    #[test]
+    fn snaps_to_the_closest_edges() {
+        assert_eq!(snapped((8, 1070), (800, 600), &[MONITOR], 10), (0, 1070));
+        assert_eq!(
+            snapped((1115, 475), (800, 600), &[MONITOR], 10),
+            (1120, 480)
+        );
+        assert_eq!(snapped((-5, -5), (800, 600), &[MONITOR], 10), (0, 0));
+    }
+
+    #[test]
+    fn snaps_onto_the_adjacent_monitor_the_window_is_mostly_on() {
+        let monitors = [MONITOR, RIGHT_MONITOR];
+        // Straddling the boundary by 5 pixels on either side.
+        assert_eq!(snapped((1125, 100), (800, 600), &monitors, 10), (1120, 100));
+        assert_eq!(snapped((1915, 100), (800, 600), &monitors, 10), (1920, 100));
+        // Flush with the right edge of the right monitor.
+        assert_eq!(snapped((3035, 100), (800, 600), &monitors, 10), (3040, 100));
+    }
+
+    #[test]
+    fn doesnt_snap_farther_than_the_threshold() {
+        assert_eq!(snapped((100, 100), (800, 600), &[MONITOR], 10), (100, 100));
+        // A window larger than the monitor only snaps within the threshold too.
+        assert_eq!(snapped((-30, 50), (2000, 1200), &[MONITOR], 10), (-30, 50));
+        assert_eq!(snapped((-30, 50), (2000, 1200), &[MONITOR], 40), (0, 50));
+    }
+
+    #[test]
+    fn doesnt_snap_without_nearby_monitors() {
+        assert_eq!(
+            snapped((5000, 5000), (800, 600), &[MONITOR], 10),
+            (5000, 5000)
+        );
+        assert_eq!(snapped((1925, 100), (800, 600), &[MONITOR], 4), (1925, 100));
+        assert_eq!(snapped((100, 100), (800, 600), &[], 10), (100, 100));
+    }
+
+    #[test]
+    fn snaps_with_a_threshold_larger_than_i32() {
+        assert_eq!(
+            snapped((100, 100), (800, 600), &[MONITOR], u32::MAX),
+            (0, 0)
+        );
+        assert_eq!(
+            snapped((5000, 5000), (800, 600), &[MONITOR], u32::MAX),
+            (1120, 480)
+        );
+    }
+
+    #[test]
+    fn centers_on_the_monitor() {
+        let (position, size) = RIGHT_MONITOR;
+        let outer_size = PhysicalSize::new(800, 600);
+        assert_eq!(
+            centered_in(position, size, outer_size),
+            PhysicalPosition::new(2480, 240)
+        );
+    }
+
+    #[test]
+    fn aligns_windows_larger_than_the_monitor_with_its_top_left_corner() {
+        let (position, size) = RIGHT_MONITOR;
+        assert_eq!(
+            centered_in(position, size, PhysicalSize::new(2000, 600)),
+            PhysicalPosition::new(1920, 240)
+        );
+        assert_eq!(
+            centered_in(position, size, PhysicalSize::new(800, 1200)),
+            PhysicalPosition::new(2480, 0)
+        );
+        assert_eq!(
+            centered_in(position, size, PhysicalSize::new(4000, 3000)),
+            PhysicalPosition::new(1920, 0)
+        );
+    }
+}
@@ -0,0 +1,13 @@
+use std::hash::Hash;
+
+#[allow(dead_code)]
+fn needs_hash<T: Eq + Hash>() {}
+
+#[test]
+fn ids_hash() {
+    // ensures that the various `..Id` types can be used as map keys
+    needs_hash::<winit::window::WindowId>();
+    needs_hash::<winit::event::DeviceId>();
+    needs_hash::<winit::monitor::MonitorHandle>();
+    needs_hash::<winit::monitor::VideoMode>();
+}